@@ -4,7 +4,7 @@ use async_channel::{unbounded, Receiver, Sender};
 use bevy::prelude::{
     AssetServer, Commands, Deref, DerefMut, Query, Res, ResMut, Resource, Transform,
 };
-use shared::action::{Action, PlayerAction};
+use shared::action::ClientCommand;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
 use tokio::net::TcpStream;
@@ -13,18 +13,12 @@ use tokio::net::TcpStream;
 pub(crate) struct MessageReceiver(pub Receiver<String>);
 
 #[derive(Resource, Clone)]
-pub(crate) struct MessageSender(pub Sender<PlayerAction>);
+pub(crate) struct MessageSender(pub Sender<ClientCommand>);
 
-pub(crate) async fn send_action(action: Action, data: Option<String>, writer: &mut OwnedWriteHalf) {
-    // Send the player's action to the server
-    // Create an action to send to the server
-    log::debug!("Sending action: {:?}", action);
-    let action = PlayerAction {
-        action_type: action,
-        data, // Add specific data if required
-    };
-
-    let serialized_action = serde_json::to_string(&action).unwrap();
+pub(crate) async fn send_action(command: ClientCommand, writer: &mut OwnedWriteHalf) {
+    // Send the player's command to the server
+    log::debug!("Sending action: {:?}", command);
+    let serialized_action = serde_json::to_string(&command).unwrap();
     if let Err(e) = writer
         .write_all((serialized_action + "\n").as_bytes())
         .await
@@ -49,7 +43,7 @@ pub(crate) async fn connect_to_server() -> (BufReader<OwnedReadHalf>, OwnedWrite
 
 async fn handle_server_communication(
     tx_server: Sender<String>,
-    rx_client: Receiver<PlayerAction>,
+    rx_client: Receiver<ClientCommand>,
     mut reader: BufReader<OwnedReadHalf>,
     mut writer: OwnedWriteHalf,
 ) -> Result<(), Box<dyn std::error::Error>> {
@@ -82,8 +76,8 @@ async fn handle_server_communication(
             action = rx_client.recv() => {
                 match action {
                     Ok(action) => {
-                        log::debug!("Sending message: {:?}", action.action_type);
-                        send_action(action.action_type, action.data, &mut writer).await;
+                        log::debug!("Sending message: {:?}", action);
+                        send_action(action, &mut writer).await;
                     }
                     Err(e) => {
                         log::debug!("No message to send");
@@ -118,9 +112,9 @@ pub(crate) fn receive_message(
     }
 }
 
-pub(crate) async fn setup_network() -> (Receiver<String>, Sender<PlayerAction>) {
+pub(crate) async fn setup_network() -> (Receiver<String>, Sender<ClientCommand>) {
     let (tx_server, rx_server) = unbounded();
-    let (tx_client, rx_client) = unbounded::<PlayerAction>();
+    let (tx_client, rx_client) = unbounded::<ClientCommand>();
 
     // Spawn the Tokio task for network communication
     tokio::spawn(async move {
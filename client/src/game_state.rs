@@ -1,13 +1,29 @@
 use crate::screens::board::{
-    add_player_banner, convert_pos_to_coords, generate_positions, spawn_players,
+    add_player_banner, convert_pos_to_coords, generate_positions, remove_player_banner,
+    spawn_board, spawn_players,
 };
+use crate::ui::auction::{remove_auction_panel, spawn_auction_panel};
+use crate::ui::buildings::refresh_building_picker;
 use crate::ui::buttons::spawn_buy_buttons;
+use crate::ui::debt::{refresh_debt_panel, remove_debt_panel, spawn_debt_panel};
+use crate::ui::income_tax::{remove_income_tax_panel, spawn_income_tax_panel};
+use crate::ui::speed_die::{remove_speed_die_panel, spawn_bus_panel, spawn_teleport_panel};
 use crate::ui::toast::{spawn_toast, ToastCount};
+use crate::ui::trade::{remove_trade_offer, spawn_trade_offer, TradeDraft};
 use bevy::prelude::*;
 use bevy::utils::default;
-use shared::action::{Action, BuyPropertyData, PlayerAction, PlayerIdentifyData};
+use shared::action::{
+    AuctionEndData, AuctionItem, AuctionStartData, BankruptData, BidData, BuildingData,
+    BuyPropertyData, DebtData, DiceRollData, DrawCardData, GameOverData, GameOverReason,
+    GameStartData, HouseRequestData, IncomeTaxData, JackpotData, JailData, MortgageData,
+    PayRentData, PlayerGoTileData, PlayerPayTaxData, ServerEvent, SpeedDieFace, StateRefreshData,
+    TradeOffer,
+};
 use shared::board::Tile::{Property, Railroad, Utility};
-use shared::maps::map1::MAP1;
+use shared::board::{count_buildings, PropertyLevel};
+use shared::cards::{CardDeck, CardEffect};
+use shared::list_const::AUCTION_MIN_INCREMENT;
+use shared::rules::GameRules;
 use std::collections::HashMap;
 use uuid::Uuid;
 
@@ -20,6 +36,7 @@ pub(crate) struct Player {
     pub(crate) entity: Entity,
     pub(crate) player_number: usize,
     pub(crate) is_bankrupt: bool,
+    pub(crate) jail_cards: u32,
 }
 
 #[derive(Resource, Debug)]
@@ -30,7 +47,24 @@ pub(crate) struct GamesState {
     pub(crate) board: Vec<shared::board::Tile>,
     pub(crate) board_entity: Vec<Entity>,
     pub(crate) can_roll: bool,
+    // The last roll was a double, the player rolls again once moved
+    pub(crate) extra_roll: bool,
     pub(crate) buy_button_node_id: Option<Entity>,
+    pub(crate) auction_node_id: Option<Entity>,
+    pub(crate) auction_min_bid: u32,
+    pub(crate) trade_draft: Option<TradeDraft>,
+    pub(crate) trade_node_id: Option<Entity>,
+    pub(crate) trade_offer: Option<TradeOffer>,
+    pub(crate) trade_offer_node_id: Option<Entity>,
+    pub(crate) debts: Vec<DebtData>,
+    pub(crate) debt_node_id: Option<Entity>,
+    pub(crate) building_node_id: Option<Entity>,
+    pub(crate) rules: GameRules,
+    pub(crate) jackpot: u32,
+    pub(crate) income_tax_node_id: Option<Entity>,
+    pub(crate) speed_die_node_id: Option<Entity>,
+    // Tile picked after rolling a triple
+    pub(crate) teleport_target: usize,
 }
 
 impl Default for GamesState {
@@ -39,10 +73,25 @@ impl Default for GamesState {
             id: Uuid::nil(),
             players: HashMap::new(),
             player_turn: Uuid::new_v4(),
-            board: MAP1.clone(),
+            board: vec![],
             board_entity: vec![],
             can_roll: false,
+            extra_roll: false,
             buy_button_node_id: None,
+            auction_node_id: None,
+            auction_min_bid: 0,
+            trade_draft: None,
+            trade_node_id: None,
+            trade_offer: None,
+            trade_offer_node_id: None,
+            debts: vec![],
+            debt_node_id: None,
+            building_node_id: None,
+            rules: GameRules::default(),
+            jackpot: 0,
+            income_tax_node_id: None,
+            speed_die_node_id: None,
+            teleport_target: 0,
         }
     }
 }
@@ -55,56 +104,127 @@ pub(crate) fn handle_message_in_game(
     mut transforms: Query<&mut Transform>,
     toast_count: ResMut<ToastCount>,
 ) {
-    let action: PlayerAction = serde_json::from_str(message).unwrap();
-    match action.action_type {
-        Action::GameStart => {
-            start_game(state, action, commands, asset_server, toast_count);
-        }
-        Action::PlayerTurn => {
-            start_player_turn(state, commands, toast_count, action);
-        }
-        Action::Identify => {
-            state.id = action.data.unwrap().parse::<Uuid>().unwrap();
-            log::debug!("Player identified with ID: {}", state.id);
-        }
-        Action::Move => {
-            move_player(
-                state,
-                &mut transforms,
-                action.data.unwrap().parse::<usize>().unwrap(),
+    let event: ServerEvent = serde_json::from_str(message).unwrap();
+    match event {
+        ServerEvent::GameStart(data) => {
+            start_game(state, data, commands, asset_server, toast_count);
+        }
+        ServerEvent::PlayerTurn(player) => {
+            start_player_turn(state, commands, toast_count, player);
+        }
+        ServerEvent::Welcome(data) => {
+            state.id = data.player;
+            log::debug!(
+                "Player identified with ID: {}, server capabilities {:?}",
+                state.id,
+                data.capabilities
+            );
+        }
+        ServerEvent::HandshakeRejected(data) => {
+            log::error!(
+                "Server protocol version {} refused the client: {}",
+                data.protocol_version,
+                data.reason
             );
+            spawn_toast(commands, data.reason, 10.0, toast_count);
         }
-        Action::PayRent => {
-            pay_rent(state, commands, toast_count, action);
+        ServerEvent::Move(position) => {
+            move_player(state, commands, &mut transforms, position);
         }
-        Action::AskBuyProperty => {
-            ask_buy_property(state, commands, action);
+        ServerEvent::PayRent(data) => {
+            pay_rent(state, commands, toast_count, data);
         }
-        Action::BuyProperty => {
-            buy_property(state, commands, action, asset_server, toast_count);
+        ServerEvent::AskBuyProperty(data) => {
+            ask_buy_property(state, commands, data);
         }
-        Action::GoToJail => {
+        ServerEvent::BuyProperty(data) => {
+            buy_property(state, commands, data, asset_server, toast_count);
+        }
+        ServerEvent::GoToJail(_) => {
             send_to_jail(state, commands, &mut transforms, toast_count);
         }
-        Action::PlayerGoTile => {
-            move_to_go_tile(state, commands, toast_count, action);
+        ServerEvent::PlayerGoTile(data) => {
+            move_to_go_tile(state, commands, toast_count, data);
+        }
+        ServerEvent::PassGo(data) => {
+            pass_go(state, commands, toast_count, data);
         }
-        Action::Roll => {
-            show_roll_data(state, commands, toast_count, action);
+        ServerEvent::Roll(data) => {
+            show_roll_data(state, commands, toast_count, data);
         }
-        Action::SkipBuyProperty => {
+        ServerEvent::SkipBuyProperty(_) => {
             skip_buy_property(state, commands, toast_count);
         }
-        Action::PayTax => {
-            pay_tax(state, commands, toast_count, action);
+        ServerEvent::PayTax(data) => {
+            pay_tax(state, commands, toast_count, data);
+        }
+        ServerEvent::PlayerBankrupt(data) => {
+            set_player_bankrupt(state, commands, asset_server, toast_count, data);
+        }
+        ServerEvent::GameOver(data) => {
+            end_game(state, commands, toast_count, data);
+        }
+        ServerEvent::DrawCard(data) => {
+            draw_card(state, commands, toast_count, data);
+        }
+        ServerEvent::BuildHouse(data) => {
+            update_building(state, commands, toast_count, data, true);
+        }
+        ServerEvent::SellHouse(data) => {
+            update_building(state, commands, toast_count, data, false);
+        }
+        ServerEvent::RequestHouse(data) => {
+            show_house_request(state, commands, toast_count, data);
+        }
+        ServerEvent::Mortgage(data) => {
+            update_mortgage(state, commands, toast_count, data, true);
+        }
+        ServerEvent::Unmortgage(data) => {
+            update_mortgage(state, commands, toast_count, data, false);
+        }
+        ServerEvent::AuctionStart(data) => {
+            start_auction(state, commands, toast_count, data);
+        }
+        ServerEvent::PlaceBid(data) => {
+            show_bid(state, commands, toast_count, data);
+        }
+        ServerEvent::AuctionEnd(data) => {
+            end_auction(state, commands, asset_server, toast_count, data);
+        }
+        ServerEvent::ProposeTrade(offer) | ServerEvent::CounterTrade(offer) => {
+            receive_trade_offer(state, commands, toast_count, offer);
         }
-        Action::PlayerBankrupt => {
-            set_player_bankrupt(state, commands, toast_count, action);
+        ServerEvent::RejectTrade(offer) => {
+            reject_trade(state, commands, toast_count, offer);
         }
-        Action::GameOver => {
-            end_game(state, commands, toast_count, action);
+        ServerEvent::TradeCompleted(offer) => {
+            complete_trade(state, commands, asset_server, toast_count, offer);
+        }
+        ServerEvent::FreeFromJail(player) => {
+            state.players.get_mut(&player).unwrap().is_in_jail = false;
+        }
+        ServerEvent::PayJailFine(data) | ServerEvent::UseJailCard(data) => {
+            leave_jail(state, commands, toast_count, data);
+        }
+        ServerEvent::AskIncomeTax(data) => {
+            ask_income_tax(state, commands, toast_count, data);
+        }
+        ServerEvent::StateRefresh(data) => {
+            refresh_state(state, commands, asset_server, data);
+        }
+        ServerEvent::Jackpot(data) => {
+            update_jackpot(state, commands, toast_count, data);
+        }
+        ServerEvent::DebtOutstanding(data) => {
+            add_debt(state, commands, toast_count, data);
+        }
+        ServerEvent::PayDebt(data) => {
+            pay_debt(state, commands, toast_count, data);
+        }
+        ServerEvent::Error(data) => {
+            log::debug!("Command refused ({:?}): {}", data.code, data.message);
+            spawn_toast(commands, data.message, 3.0, toast_count);
         }
-        _ => {}
     }
 }
 
@@ -129,13 +249,14 @@ fn pay_tax(
     state: &mut GamesState,
     commands: &mut Commands,
     toast_count: ResMut<ToastCount>,
-    action: PlayerAction,
+    data: PlayerPayTaxData,
 ) {
-    let data =
-        serde_json::from_str::<shared::action::PlayerPayTaxData>(&action.data.unwrap()).unwrap();
     let player = state.players.get_mut(&data.player).unwrap();
     player.money -= data.amount;
     log::debug!("Player {} paid {} tax", data.player, data.amount);
+    if data.player == state.id {
+        remove_income_tax_panel(commands, state);
+    }
     spawn_toast(
         commands,
         format!(
@@ -152,17 +273,43 @@ fn show_roll_data(
     state: &mut GamesState,
     commands: &mut Commands,
     toast_count: ResMut<ToastCount>,
-    action: PlayerAction,
+    data: DiceRollData,
 ) {
-    let data = serde_json::from_str::<shared::action::DiceRollData>(&action.data.unwrap()).unwrap();
     log::debug!("Player rolled {} and {}", data.dice1, data.dice2);
+    state.extra_roll = data.extra_roll;
+    let speed_die = match data.speed_die {
+        Some(SpeedDieFace::Number(number)) if number == data.dice1 && data.dice1 == data.dice2 => {
+            " and a triple: teleport anywhere".to_string()
+        }
+        Some(SpeedDieFace::Number(number)) => format!(" and {number}"),
+        Some(SpeedDieFace::MrMonopoly) => " and Mr. Monopoly".to_string(),
+        Some(SpeedDieFace::Bus) => " and the bus".to_string(),
+        None => String::new(),
+    };
+    if state.player_turn == state.id {
+        match data.speed_die {
+            Some(SpeedDieFace::Number(number))
+                if number == data.dice1 && data.dice1 == data.dice2 =>
+            {
+                spawn_teleport_panel(commands, state);
+            }
+            Some(SpeedDieFace::Bus) => spawn_bus_panel(commands, state, data.dice1, data.dice2),
+            _ => {}
+        }
+    }
     spawn_toast(
         commands,
         format!(
-            "{} rolled {} and {}",
+            "{} rolled {} and {}{}{}",
             state.players.get(&state.player_turn).unwrap().name,
             data.dice1,
-            data.dice2
+            data.dice2,
+            speed_die,
+            if data.extra_roll {
+                ", doubles: roll again!"
+            } else {
+                ""
+            }
         ),
         2.0,
         toast_count,
@@ -173,10 +320,8 @@ fn move_to_go_tile(
     state: &mut GamesState,
     commands: &mut Commands,
     toast_count: ResMut<ToastCount>,
-    action: PlayerAction,
+    data: PlayerGoTileData,
 ) {
-    let data =
-        serde_json::from_str::<shared::action::PlayerGoTileData>(&action.data.unwrap()).unwrap();
     let player = state.players.get_mut(&data.player).unwrap();
     player.money += data.amount;
     log::debug!("Player {} got {} money", data.player, data.amount);
@@ -197,6 +342,23 @@ fn move_to_go_tile(
     );
 }
 
+fn pass_go(
+    state: &mut GamesState,
+    commands: &mut Commands,
+    toast_count: ResMut<ToastCount>,
+    data: PlayerGoTileData,
+) {
+    let player = state.players.get_mut(&data.player).unwrap();
+    player.money += data.amount;
+    log::debug!("Player {} passed Go and got {}", data.player, data.amount);
+    spawn_toast(
+        commands,
+        format!("{} passed Go and collected {}!", player.name, data.amount),
+        2.0,
+        toast_count,
+    );
+}
+
 fn send_to_jail(
     state: &mut GamesState,
     commands: &mut Commands,
@@ -226,8 +388,11 @@ fn send_to_jail(
     );
 }
 
-fn ask_buy_property(state: &mut GamesState, commands: &mut Commands, action: PlayerAction) {
-    let buy_property_data: BuyPropertyData = serde_json::from_str(&action.data.unwrap()).unwrap();
+fn ask_buy_property(
+    state: &mut GamesState,
+    commands: &mut Commands,
+    buy_property_data: BuyPropertyData,
+) {
     log::debug!(
         "Player {} asked to buy property {}",
         buy_property_data.player,
@@ -242,9 +407,8 @@ fn pay_rent(
     state: &mut GamesState,
     commands: &mut Commands,
     toast_count: ResMut<ToastCount>,
-    action: PlayerAction,
+    data: PayRentData,
 ) {
-    let data: shared::action::PayRentData = serde_json::from_str(&action.data.unwrap()).unwrap();
     let rent_price = data.rent;
     state.players.get_mut(&data.player).unwrap().money -= rent_price;
     state.players.get_mut(&data.owner).unwrap().money += rent_price;
@@ -270,13 +434,40 @@ fn pay_rent(
 fn set_player_bankrupt(
     state: &mut GamesState,
     commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
     toast_count: ResMut<ToastCount>,
-    action: PlayerAction,
+    data: BankruptData,
 ) {
-    let data = action.data.unwrap().parse::<Uuid>().unwrap();
-    let player = state.players.get_mut(&data).unwrap();
+    state.debts.retain(|debt| debt.debtor != data.player);
+    if data.player == state.id {
+        remove_debt_panel(commands, state);
+        remove_income_tax_panel(commands, state);
+    }
+    for position in &data.tiles {
+        let tile = &mut state.board[*position as usize];
+        if let Property { level, .. } = tile {
+            *level = PropertyLevel::None;
+        }
+        tile.set_owner(data.creditor);
+        match data.creditor {
+            Some(creditor) => {
+                add_player_banner(commands, asset_server, state, creditor, *position as usize)
+            }
+            None => {
+                tile.set_mortgaged(false);
+                remove_player_banner(commands, state, *position as usize);
+            }
+        }
+    }
+    if let Some(creditor) = data.creditor {
+        let creditor = state.players.get_mut(&creditor).unwrap();
+        creditor.money += data.money;
+        creditor.jail_cards += data.jail_cards;
+    }
+    let player = state.players.get_mut(&data.player).unwrap();
     player.is_bankrupt = true;
     player.money = 0;
+    player.jail_cards = 0;
     log::debug!("Player {} is bankrupt", player.name);
     spawn_toast(
         commands,
@@ -286,33 +477,535 @@ fn set_player_bankrupt(
     );
 }
 
+fn refresh_state(
+    state: &mut GamesState,
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    data: StateRefreshData,
+) {
+    log::debug!("Game state refreshed by the server");
+    for player_data in data.players {
+        if let Some(player) = state.players.get_mut(&player_data.id) {
+            player.money = player_data.money;
+        }
+    }
+    for (position, tile) in data.board.into_iter().enumerate() {
+        if tile.owner() == state.board[position].owner() {
+            continue;
+        }
+        match tile.owner() {
+            Some(owner) => add_player_banner(commands, asset_server, state, owner, position),
+            None => remove_player_banner(commands, state, position),
+        }
+        state.board[position] = tile;
+    }
+}
+
 fn end_game(
     state: &mut GamesState,
     commands: &mut Commands,
     toast_count: ResMut<ToastCount>,
-    action: PlayerAction,
+    data: GameOverData,
 ) {
-    let winner = state
-        .players
-        .get(&action.data.unwrap().parse::<Uuid>().unwrap())
-        .unwrap();
-    log::debug!("Game over!");
+    log::debug!("Game over: {:?}", data.reason);
+    let reason = match data.reason {
+        GameOverReason::LastPlayerStanding => "last player standing",
+        GameOverReason::TurnLimit => "turn limit reached",
+        GameOverReason::TimeLimit => "time is up",
+    };
+    let standings = data
+        .standings
+        .iter()
+        .enumerate()
+        .map(|(rank, standing)| {
+            format!(
+                "{}. {}: {}{}",
+                rank + 1,
+                state.players.get(&standing.player).unwrap().name,
+                standing.net_worth,
+                if standing.is_bankrupt {
+                    " (bankrupt)"
+                } else {
+                    ""
+                }
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
     spawn_toast(
         commands,
-        format!("Game over! The winner is {}", winner.name),
+        format!(
+            "Game over ({reason})! The winner is {}\n{standings}",
+            state.players.get(&data.winner).unwrap().name
+        ),
         20.0,
         toast_count,
     );
 }
 
+fn draw_card(
+    state: &mut GamesState,
+    commands: &mut Commands,
+    toast_count: ResMut<ToastCount>,
+    data: DrawCardData,
+) {
+    log::debug!("Player {} drew card {:?}", data.player, data.card);
+    // Movements and jail are sent as separate actions, only money is applied here
+    match data.card.effect {
+        CardEffect::Collect(amount) => {
+            state.players.get_mut(&data.player).unwrap().money += amount;
+        }
+        CardEffect::Pay(amount) => {
+            pay_bank(state, data.player, amount);
+        }
+        CardEffect::Repairs {
+            per_house,
+            per_hotel,
+        } => {
+            let (houses, hotels) = count_buildings(&state.board, data.player);
+            pay_bank(state, data.player, houses * per_house + hotels * per_hotel);
+        }
+        CardEffect::PayEachPlayer(amount) => {
+            let others: Vec<Uuid> = state
+                .players
+                .iter()
+                .filter(|(id, player)| **id != data.player && !player.is_bankrupt)
+                .map(|(id, _)| *id)
+                .collect();
+            let total = amount * others.len() as u32;
+            if state.players.get(&data.player).unwrap().money >= total {
+                state.players.get_mut(&data.player).unwrap().money -= total;
+                for id in others {
+                    state.players.get_mut(&id).unwrap().money += amount;
+                }
+            }
+        }
+        CardEffect::CollectFromEachPlayer(amount) => {
+            let mut collected = 0;
+            for (id, player) in state.players.iter_mut() {
+                if *id != data.player && !player.is_bankrupt && player.money >= amount {
+                    player.money -= amount;
+                    collected += amount;
+                }
+            }
+            state.players.get_mut(&data.player).unwrap().money += collected;
+        }
+        CardEffect::GetOutOfJailFree => {
+            state.players.get_mut(&data.player).unwrap().jail_cards += 1;
+        }
+        CardEffect::MoveTo(_) | CardEffect::MoveBack(_) | CardEffect::GoToJail => {}
+    }
+    let deck = match data.deck {
+        CardDeck::Chance => "Chance",
+        CardDeck::CommunityChest => "Community Chest",
+    };
+    spawn_toast(
+        commands,
+        format!(
+            "{} drew a {} card: {}",
+            state.players.get(&data.player).unwrap().name,
+            deck,
+            data.card.description
+        ),
+        3.0,
+        toast_count,
+    );
+}
+
+fn update_building(
+    state: &mut GamesState,
+    commands: &mut Commands,
+    toast_count: ResMut<ToastCount>,
+    data: BuildingData,
+    is_build: bool,
+) {
+    if let Property { level, .. } = &mut state.board[data.position as usize] {
+        *level = data.level.clone();
+    }
+    if data.player == state.id {
+        refresh_debt_panel(commands, state);
+        refresh_building_picker(commands, state);
+    }
+    let player = state.players.get_mut(&data.player).unwrap();
+    if is_build {
+        player.money -= data.price;
+    } else {
+        player.money += data.price;
+    }
+    log::debug!(
+        "Player {} changed tile {} to {:?}",
+        data.player,
+        data.position,
+        data.level
+    );
+    spawn_toast(
+        commands,
+        format!(
+            "{} {} a building",
+            player.name,
+            if is_build { "built" } else { "sold" }
+        ),
+        2.0,
+        toast_count,
+    );
+}
+
+fn show_house_request(
+    state: &mut GamesState,
+    commands: &mut Commands,
+    toast_count: ResMut<ToastCount>,
+    data: HouseRequestData,
+) {
+    log::debug!(
+        "Player {} asked for a house on tile {}",
+        data.player,
+        data.position
+    );
+    spawn_toast(
+        commands,
+        format!(
+            "{} asks for one of the last houses, build now to compete for it",
+            state.players.get(&data.player).unwrap().name
+        ),
+        3.0,
+        toast_count,
+    );
+}
+
+fn update_mortgage(
+    state: &mut GamesState,
+    commands: &mut Commands,
+    toast_count: ResMut<ToastCount>,
+    data: MortgageData,
+    is_mortgage: bool,
+) {
+    state.board[data.position as usize].set_mortgaged(is_mortgage);
+    if data.player == state.id {
+        refresh_debt_panel(commands, state);
+    }
+    let player = state.players.get_mut(&data.player).unwrap();
+    if is_mortgage {
+        player.money += data.amount;
+    } else {
+        player.money -= data.amount;
+    }
+    log::debug!(
+        "Player {} changed the mortgage on tile {}",
+        data.player,
+        data.position
+    );
+    spawn_toast(
+        commands,
+        format!(
+            "{} {} a property for {}",
+            player.name,
+            if is_mortgage {
+                "mortgaged"
+            } else {
+                "lifted the mortgage on"
+            },
+            data.amount
+        ),
+        2.0,
+        toast_count,
+    );
+}
+
+fn auction_item_name(item: &AuctionItem) -> String {
+    match item {
+        AuctionItem::House => "a house".to_string(),
+        AuctionItem::Property(_) => "the property".to_string(),
+    }
+}
+
+fn start_auction(
+    state: &mut GamesState,
+    commands: &mut Commands,
+    toast_count: ResMut<ToastCount>,
+    data: AuctionStartData,
+) {
+    log::debug!("Auction started for {:?}", data.item);
+    state.auction_min_bid = data.starting_price;
+    if data.bidders.contains(&state.id) {
+        spawn_auction_panel(commands, state);
+    }
+    spawn_toast(
+        commands,
+        format!(
+            "Auction for {} starts at {}",
+            auction_item_name(&data.item),
+            data.starting_price
+        ),
+        3.0,
+        toast_count,
+    );
+}
+
+fn show_bid(
+    state: &mut GamesState,
+    commands: &mut Commands,
+    toast_count: ResMut<ToastCount>,
+    data: BidData,
+) {
+    log::debug!("Player {} bid {}", data.player, data.amount);
+    state.auction_min_bid = data.amount + AUCTION_MIN_INCREMENT;
+    spawn_toast(
+        commands,
+        format!(
+            "{} bid {}",
+            state.players.get(&data.player).unwrap().name,
+            data.amount
+        ),
+        2.0,
+        toast_count,
+    );
+}
+
+fn end_auction(
+    state: &mut GamesState,
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    toast_count: ResMut<ToastCount>,
+    data: AuctionEndData,
+) {
+    log::debug!("Auction ended: {:?}", data);
+    remove_auction_panel(commands, state);
+    // Houses are placed with a separate BuildHouse action
+    if let (AuctionItem::Property(position), Some(winner)) = (&data.item, data.winner) {
+        let player = state.players.get_mut(&winner).unwrap();
+        if player.money >= data.amount {
+            player.money -= data.amount;
+            state.board[*position as usize].set_owner(Some(winner));
+            add_player_banner(commands, asset_server, state, winner, *position as usize);
+        }
+    }
+    let message = match data.winner {
+        Some(winner) => format!(
+            "{} won {} for {}",
+            state.players.get(&winner).unwrap().name,
+            auction_item_name(&data.item),
+            data.amount
+        ),
+        None => format!("Nobody bid for {}", auction_item_name(&data.item)),
+    };
+    spawn_toast(commands, message, 3.0, toast_count);
+}
+
+fn receive_trade_offer(
+    state: &mut GamesState,
+    commands: &mut Commands,
+    toast_count: ResMut<ToastCount>,
+    offer: TradeOffer,
+) {
+    log::debug!("Trade offer received: {:?}", offer);
+    let message = format!(
+        "{} proposed a trade to {}",
+        state.players.get(&offer.from).unwrap().name,
+        state.players.get(&offer.to).unwrap().name
+    );
+    if offer.to == state.id {
+        spawn_trade_offer(commands, state, offer);
+    }
+    spawn_toast(commands, message, 2.0, toast_count);
+}
+
+fn reject_trade(
+    state: &mut GamesState,
+    commands: &mut Commands,
+    toast_count: ResMut<ToastCount>,
+    offer: TradeOffer,
+) {
+    log::debug!("Trade {} rejected", offer.id);
+    if state.trade_offer.as_ref().map(|pending| pending.id) == Some(offer.id) {
+        remove_trade_offer(commands, state);
+    }
+    if offer.from == state.id || offer.to == state.id {
+        spawn_toast(
+            commands,
+            "The trade was cancelled".to_string(),
+            2.0,
+            toast_count,
+        );
+    }
+}
+
+fn complete_trade(
+    state: &mut GamesState,
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    toast_count: ResMut<ToastCount>,
+    offer: TradeOffer,
+) {
+    log::debug!("Trade {} completed", offer.id);
+    let from = state.players.get_mut(&offer.from).unwrap();
+    from.money = from.money - offer.offered.money + offer.requested.money;
+    from.jail_cards = from.jail_cards - offer.offered.jail_cards + offer.requested.jail_cards;
+    let to = state.players.get_mut(&offer.to).unwrap();
+    to.money = to.money - offer.requested.money + offer.offered.money;
+    to.jail_cards = to.jail_cards - offer.requested.jail_cards + offer.offered.jail_cards;
+    for (tiles, new_owner) in [
+        (&offer.offered.tiles, offer.to),
+        (&offer.requested.tiles, offer.from),
+    ] {
+        for position in tiles {
+            state.board[*position as usize].set_owner(Some(new_owner));
+            add_player_banner(commands, asset_server, state, new_owner, *position as usize);
+        }
+    }
+    if state.trade_offer.as_ref().map(|pending| pending.id) == Some(offer.id) {
+        remove_trade_offer(commands, state);
+    }
+    spawn_toast(
+        commands,
+        format!(
+            "{} and {} completed a trade",
+            state.players.get(&offer.from).unwrap().name,
+            state.players.get(&offer.to).unwrap().name
+        ),
+        2.0,
+        toast_count,
+    );
+}
+
+fn leave_jail(
+    state: &mut GamesState,
+    commands: &mut Commands,
+    toast_count: ResMut<ToastCount>,
+    data: JailData,
+) {
+    let player = state.players.get_mut(&data.player).unwrap();
+    player.is_in_jail = false;
+    player.money -= data.fine;
+    let message = if data.card.is_some() {
+        player.jail_cards -= 1;
+        format!("{} used a Get Out of Jail Free card", player.name)
+    } else {
+        format!("{} paid {} to leave jail", player.name, data.fine)
+    };
+    log::debug!("Player {} left jail", data.player);
+    spawn_toast(commands, message, 2.0, toast_count);
+}
+
+fn ask_income_tax(
+    state: &mut GamesState,
+    commands: &mut Commands,
+    toast_count: ResMut<ToastCount>,
+    data: IncomeTaxData,
+) {
+    log::debug!(
+        "Player {} must choose how to pay the income tax",
+        data.player
+    );
+    if data.player == state.id {
+        spawn_income_tax_panel(commands, state, &data);
+    }
+    spawn_toast(
+        commands,
+        format!(
+            "{} pays the income tax: {} or {}% of their net worth",
+            state.players.get(&data.player).unwrap().name,
+            data.flat,
+            data.rate
+        ),
+        2.0,
+        toast_count,
+    );
+}
+
+fn update_jackpot(
+    state: &mut GamesState,
+    commands: &mut Commands,
+    toast_count: ResMut<ToastCount>,
+    data: JackpotData,
+) {
+    state.jackpot = data.pot;
+    let Some(winner) = data.collected_by else {
+        return;
+    };
+    let player = state.players.get_mut(&winner).unwrap();
+    player.money += data.amount;
+    log::debug!("Player {} collected the jackpot of {}", winner, data.amount);
+    spawn_toast(
+        commands,
+        format!(
+            "{} collected the Free Parking jackpot of {}!",
+            player.name, data.amount
+        ),
+        3.0,
+        toast_count,
+    );
+}
+
+fn creditor_name(state: &GamesState, creditor: Option<Uuid>) -> String {
+    creditor
+        .and_then(|id| state.players.get(&id))
+        .map_or("the bank".to_string(), |player| player.name.clone())
+}
+
+fn add_debt(
+    state: &mut GamesState,
+    commands: &mut Commands,
+    toast_count: ResMut<ToastCount>,
+    data: DebtData,
+) {
+    log::debug!("Player {} owes {}", data.debtor, data.amount);
+    let message = format!(
+        "{} must raise {} to pay {}",
+        state.players.get(&data.debtor).unwrap().name,
+        data.amount,
+        creditor_name(state, data.creditor)
+    );
+    if data.debtor == state.id {
+        remove_income_tax_panel(commands, state);
+        spawn_debt_panel(commands, state);
+    }
+    state.debts.push(data);
+    spawn_toast(commands, message, 3.0, toast_count);
+}
+
+fn pay_debt(
+    state: &mut GamesState,
+    commands: &mut Commands,
+    toast_count: ResMut<ToastCount>,
+    data: DebtData,
+) {
+    log::debug!("Player {} paid a debt of {}", data.debtor, data.amount);
+    state.players.get_mut(&data.debtor).unwrap().money -= data.amount;
+    if let Some(creditor) = data.creditor {
+        state.players.get_mut(&creditor).unwrap().money += data.amount;
+    }
+    if let Some(index) = state.debts.iter().position(|debt| *debt == data) {
+        state.debts.remove(index);
+    }
+    if !state.debts.iter().any(|debt| debt.debtor == state.id) {
+        remove_debt_panel(commands, state);
+    }
+    let message = format!(
+        "{} paid {} to {}",
+        state.players.get(&data.debtor).unwrap().name,
+        data.amount,
+        creditor_name(state, data.creditor)
+    );
+    spawn_toast(commands, message, 2.0, toast_count);
+}
+
+fn pay_bank(state: &mut GamesState, player: Uuid, amount: u32) {
+    let player = state.players.get_mut(&player).unwrap();
+    if player.money >= amount {
+        player.money -= amount;
+    }
+}
+
 fn start_player_turn(
     state: &mut GamesState,
     commands: &mut Commands,
     toast_count: ResMut<ToastCount>,
-    action: PlayerAction,
+    player: Uuid,
 ) {
     state.can_roll = true;
-    state.player_turn = action.data.unwrap().parse::<Uuid>().unwrap();
+    state.extra_roll = false;
+    remove_speed_die_panel(commands, state);
+    state.player_turn = player;
     log::debug!("Player {} turn", state.player_turn);
     spawn_toast(
         commands,
@@ -328,12 +1021,10 @@ fn start_player_turn(
 fn buy_property(
     state: &mut GamesState,
     commands: &mut Commands,
-    action: PlayerAction,
+    buy_property_data: BuyPropertyData,
     asset_server: &Res<AssetServer>,
     toast_count: ResMut<ToastCount>,
 ) {
-    let buy_property_data: BuyPropertyData =
-        serde_json::from_str(action.data.unwrap().as_str()).unwrap();
     let player = state.players.get_mut(&buy_property_data.player).unwrap();
     let mut tile_owner: &mut Option<Uuid> = &mut None;
     let mut tile_cost = 0;
@@ -364,10 +1055,22 @@ fn buy_property(
         2.0,
         toast_count,
     );
-    add_player_banner(commands, asset_server, state);
+    add_player_banner(
+        commands,
+        asset_server,
+        state,
+        buy_property_data.player,
+        buy_property_data.position as usize,
+    );
 }
 
-fn move_player(state: &mut GamesState, transforms: &mut Query<&mut Transform>, roll: usize) {
+fn move_player(
+    state: &mut GamesState,
+    commands: &mut Commands,
+    transforms: &mut Query<&mut Transform>,
+    roll: usize,
+) {
+    remove_speed_die_panel(commands, state);
     log::debug!("uuid: {:?}", state.player_turn);
     state.players.get_mut(&state.player_turn).unwrap().position = roll;
     log::debug!(
@@ -376,28 +1079,30 @@ fn move_player(state: &mut GamesState, transforms: &mut Query<&mut Transform>, r
         state.board[state.players.get(&state.player_turn).unwrap().position]
     );
     // Get player and move it
-    let position = generate_positions();
+    let position = generate_positions(state.board.len());
     log::debug!("Position x: {:?}", position[roll].0);
     log::debug!("Position y: {:?}", position[roll].1);
-    let pos = convert_pos_to_coords(roll);
+    let pos = convert_pos_to_coords(state.board.len(), roll);
     *transforms
         .get_mut(state.players.get(&state.player_turn).unwrap().entity)
         .unwrap() = Transform {
         translation: Vec3::new(pos.0, pos.1, 32f32),
         ..default()
     };
-    state.can_roll = false;
+    state.can_roll = state.extra_roll;
 }
 
 fn start_game(
     state: &mut GamesState,
-    action: PlayerAction,
+    data: GameStartData,
     commands: &mut Commands,
     asset_server: &Res<AssetServer>,
     toast_count: ResMut<ToastCount>,
 ) {
-    let data = action.data.unwrap();
-    let players_data = serde_json::from_str::<Vec<PlayerIdentifyData>>(&data).unwrap();
+    let players_data = data.players;
+    state.rules = data.rules;
+    state.board = data.board;
+    spawn_board(commands, asset_server, state);
     log::debug!("Game started with {} players", players_data.len());
     log::debug!("Players ID: {:?}", players_data);
 
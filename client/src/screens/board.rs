@@ -2,9 +2,15 @@ use crate::communication::MessageSender;
 use crate::game_state::{GamesState, Player};
 use crate::screens::GameStateEnum;
 use crate::tools::despawn_screen;
+use crate::ui::auction::{auction_button_system, auction_text_system};
+use crate::ui::buildings::{building_button_system, open_building_picker};
 use crate::ui::buttons::button_system;
+use crate::ui::debt::{bankruptcy_button_system, debt_asset_button_system, debt_text_system};
+use crate::ui::income_tax::income_tax_button_system;
 use crate::ui::money::MoneyText;
 use crate::ui::name::{name_system, NameText};
+use crate::ui::speed_die::{speed_die_button_system, teleport_text_system};
+use crate::ui::trade::{open_trade_dialog, trade_button_system, trade_dialog_system};
 use crate::ui::{money, toast};
 use crate::{communication, helpers};
 use bevy::asset::Handle;
@@ -12,13 +18,12 @@ use bevy::image::Image;
 use bevy::input::ButtonInput;
 use bevy::prelude::*;
 use bevy::tasks::AsyncComputeTaskPool;
-use shared::action::{Action, PlayerAction, PlayerIdentifyData};
+use shared::action::{ClientCommand, PlayerStartData};
 use shared::board::Tile;
-use shared::maps::map1::MAP1;
+use uuid::Uuid;
 
 pub(crate) const TILE_WIDTH: f32 = 110.0; // Width of an isometric tile
 pub(crate) const TILE_HEIGHT: f32 = 63.0; // Height of an isometric tile
-pub(crate) const GRID_SIZE: usize = 11; // Number of tiles along one edge (must be odd)
 
 #[derive(Component)]
 pub(crate) struct OnGameScreen;
@@ -31,8 +36,8 @@ const SPRITES_PATH: [&str; 5] = [
     "sprites/alienYellow_badge2.png",
 ];
 
-fn get_texture(asset_server: &Res<AssetServer>, i: usize) -> Handle<Image> {
-    match MAP1[i] {
+fn get_texture(asset_server: &AssetServer, tile: &Tile) -> Handle<Image> {
+    match tile {
         Tile::Property { .. } => asset_server.load("textures/voxelTile_55.png"),
         Tile::Chance(_) => asset_server.load("textures/platformerTile_36.png"),
         Tile::Jail => asset_server.load("textures/platformerTile_33.png"),
@@ -51,35 +56,32 @@ pub fn game_plugin(app: &mut App) {
         .add_systems(OnExit(GameStateEnum::Game), despawn_screen::<OnGameScreen>)
         .add_systems(Update, communication::receive_message)
         .add_systems(Update, button_system)
-        .add_systems(Update, helpers::camera::movement)
+        .add_systems(Update, (auction_button_system, auction_text_system))
+        .add_systems(
+            Update,
+            (
+                bankruptcy_button_system,
+                debt_asset_button_system,
+                debt_text_system,
+            ),
+        )
+        .add_systems(Update, (open_building_picker, building_button_system))
+        .add_systems(Update, income_tax_button_system)
+        .add_systems(Update, (speed_die_button_system, teleport_text_system))
+        .add_systems(
+            Update,
+            (open_trade_dialog, trade_button_system, trade_dialog_system),
+        )
+        .add_systems(Update, (helpers::camera::movement, center_camera))
         .add_systems(Update, money::scoreboard_system)
         .add_systems(Update, name_system)
         .add_systems(Update, toast::update_toasts)
-        .add_systems(Update, roll_dice);
+        .add_systems(Update, roll_dice)
+        .add_systems(Update, leave_jail)
+        .add_systems(Update, manage_property);
 }
 
-pub(crate) fn game_setup(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    mut games_state: ResMut<GamesState>,
-) {
-    for (i, (col, row)) in generate_positions().iter().enumerate() {
-        let x = (col - row) * (TILE_WIDTH / 2.0);
-        let y = -(col + row) * (TILE_HEIGHT / 2.0);
-        games_state.board_entity.push(
-            commands
-                .spawn((
-                    Sprite {
-                        image: get_texture(&asset_server, i),
-                        ..Default::default()
-                    },
-                    Transform::from_xyz(x, y, row + col),
-                    Name::new(format!("Tile_{}", i)),
-                ))
-                .id(),
-        );
-    }
-
+pub(crate) fn game_setup(mut commands: Commands) {
     commands.spawn((
         Text::new("Money:"),
         Node {
@@ -103,46 +105,103 @@ pub(crate) fn game_setup(
     ));
 }
 
-pub(crate) fn generate_positions() -> Vec<(f32, f32)> {
-    let mut positions = Vec::new();
-    for col in 0..GRID_SIZE {
-        positions.push((col as f32, 0f32));
-    }
-
-    // Right column (top to bottom)
-    for row in 1..GRID_SIZE {
-        positions.push(((GRID_SIZE - 1) as f32, row as f32));
+// Tiles are spawned from the board the server sent at game start
+pub(crate) fn spawn_board(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    state: &mut GamesState,
+) {
+    for (i, (col, row)) in generate_positions(state.board.len()).iter().enumerate() {
+        let x = (col - row) * (TILE_WIDTH / 2.0);
+        let y = -(col + row) * (TILE_HEIGHT / 2.0);
+        state.board_entity.push(
+            commands
+                .spawn((
+                    Sprite {
+                        image: get_texture(asset_server, &state.board[i]),
+                        ..Default::default()
+                    },
+                    Transform::from_xyz(x, y, row + col),
+                    Name::new(format!("Tile_{}", i)),
+                ))
+                .id(),
+        );
     }
+}
 
-    // Bottom row (right to left)
-    for col in (0..GRID_SIZE - 1).rev() {
-        positions.push((col as f32, (GRID_SIZE - 1) as f32));
-    }
+// Tiles go around a rectangle clockwise from the top corner, one grid cell each. The sides are
+// as close to a square as the tile count allows, an odd count leaves the last cell empty
+pub(crate) fn generate_positions(tile_count: usize) -> Vec<(f32, f32)> {
+    let half = tile_count.div_ceil(2);
+    let width = half.div_ceil(2).max(1);
+    let height = half.saturating_sub(width).max(1);
+    (0..tile_count)
+        .map(|i| {
+            let (col, row) = if i < width {
+                // Top row (left to right)
+                (i, 0)
+            } else if i < width + height {
+                // Right column (top to bottom)
+                (width, i - width)
+            } else if i < 2 * width + height {
+                // Bottom row (right to left)
+                (2 * width + height - i, height)
+            } else {
+                // Left column (bottom to top)
+                (0, (2 * (width + height)).saturating_sub(i))
+            };
+            (col as f32, row as f32)
+        })
+        .collect()
+}
 
-    // Left column (bottom to top)
-    for row in (1..GRID_SIZE - 1).rev() {
-        positions.push((0f32, row as f32));
-    }
-    positions
+// Center of the board in world coordinates
+pub(crate) fn board_center(tile_count: usize) -> (f32, f32) {
+    let positions = generate_positions(tile_count);
+    let (col, row) = positions.iter().fold((0f32, 0f32), |max, (col, row)| {
+        (max.0.max(*col), max.1.max(*row))
+    });
+    (
+        (col - row) / 2.0 * TILE_WIDTH / 2.0,
+        -(col + row) / 2.0 * TILE_HEIGHT / 2.0,
+    )
 }
 
-pub(crate) fn convert_pos_to_coords(pos: usize) -> (f32, f32) {
-    let positions = generate_positions();
+pub(crate) fn convert_pos_to_coords(tile_count: usize, pos: usize) -> (f32, f32) {
+    let positions = generate_positions(tile_count);
     let x = (positions[pos].0 - positions[pos].1) * (TILE_WIDTH / 2.0);
     let y = -(positions[pos].0 + positions[pos].1) * (TILE_HEIGHT / 2.0) + TILE_HEIGHT / 2.0;
     (x, y)
 }
 
+// Moves the camera over the board once its layout is known
+pub(crate) fn center_camera(
+    games_state: Res<GamesState>,
+    mut laid_out: Local<usize>,
+    mut query: Query<&mut Transform, With<Camera>>,
+) {
+    let tile_count = games_state.board.len();
+    if tile_count == *laid_out {
+        return;
+    }
+    *laid_out = tile_count;
+    let (x, y) = board_center(tile_count);
+    for mut transform in query.iter_mut() {
+        transform.translation.x = x;
+        transform.translation.y = y;
+    }
+}
+
 pub(crate) fn spawn_players(
     commands: &mut Commands,
     asset_server: &AssetServer,
-    players_data: Vec<PlayerIdentifyData>,
+    players_data: Vec<PlayerStartData>,
     state: &mut GamesState,
 ) {
     for (i, data) in players_data.iter().enumerate() {
         log::debug!("Spawning player {}", i);
         let player_texture = asset_server.load(SPRITES_PATH[i]);
-        let pos = convert_pos_to_coords(0);
+        let pos = convert_pos_to_coords(state.board.len(), data.position);
         let player_entity = commands
             .spawn((
                 Sprite {
@@ -157,12 +216,13 @@ pub(crate) fn spawn_players(
             data.id,
             Player {
                 name: data.name.clone(),
-                money: 1500,
-                position: 0,
+                money: data.money,
+                position: data.position,
                 is_in_jail: false,
                 entity: player_entity,
                 player_number: i,
                 is_bankrupt: false,
+                jail_cards: 0,
             },
         );
     }
@@ -183,31 +243,85 @@ pub(crate) fn roll_dice(
         let task_pool = AsyncComputeTaskPool::get();
         task_pool
             .spawn(async move {
-                sender
-                    .0
-                    .send(PlayerAction {
-                        action_type: Action::Roll,
-                        data: None,
-                    })
-                    .await
-                    .unwrap();
+                sender.0.send(ClientCommand::Roll).await.unwrap();
             })
             .detach();
     }
 }
 
+// Pay the fine (F) or use a Get Out of Jail Free card (J) before rolling
+pub(crate) fn leave_jail(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    sender: Res<MessageSender>,
+    games_state: Res<GamesState>,
+) {
+    let command = if keyboard_input.just_pressed(KeyCode::KeyF) {
+        ClientCommand::PayJailFine
+    } else if keyboard_input.just_pressed(KeyCode::KeyJ) {
+        ClientCommand::UseJailCard
+    } else {
+        return;
+    };
+    let in_jail = games_state
+        .players
+        .get(&games_state.id)
+        .is_some_and(|player| player.is_in_jail);
+    if games_state.player_turn != games_state.id || !in_jail {
+        return;
+    }
+    let sender = sender.clone();
+    let task_pool = AsyncComputeTaskPool::get();
+    task_pool
+        .spawn(async move {
+            sender.0.send(command).await.unwrap();
+        })
+        .detach();
+}
+
+// Mortgage (M) or lift the mortgage (U) on the property the player is standing on, buildings
+// go through the picker
+pub(crate) fn manage_property(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    sender: Res<MessageSender>,
+    games_state: Res<GamesState>,
+) {
+    let command: fn(usize) -> ClientCommand = if keyboard_input.just_pressed(KeyCode::KeyM) {
+        ClientCommand::Mortgage
+    } else if keyboard_input.just_pressed(KeyCode::KeyU) {
+        ClientCommand::Unmortgage
+    } else {
+        return;
+    };
+    if games_state.player_turn != games_state.id {
+        return;
+    }
+    let Some(player) = games_state.players.get(&games_state.id) else {
+        return;
+    };
+    let command = command(player.position);
+    let sender = sender.clone();
+    let task_pool = AsyncComputeTaskPool::get();
+    task_pool
+        .spawn(async move {
+            sender.0.send(command).await.unwrap();
+        })
+        .detach();
+}
+
 pub(crate) fn add_player_banner(
     commands: &mut Commands,
     asset_server: &Res<AssetServer>,
     state: &mut GamesState,
+    owner: Uuid,
+    position: usize,
 ) {
-    let player_texture = asset_server
-        .load(SPRITES_PATH[state.players.get(&state.player_turn).unwrap().player_number]);
+    let player_texture =
+        asset_server.load(SPRITES_PATH[state.players.get(&owner).unwrap().player_number]);
     commands
-        .entity(state.board_entity[state.players.get(&state.player_turn).unwrap().position])
+        .entity(state.board_entity[position])
         .despawn_descendants();
     commands
-        .entity(state.board_entity[state.players.get(&state.player_turn).unwrap().position])
+        .entity(state.board_entity[position])
         .with_children(|parent| {
             parent.spawn((
                 Sprite {
@@ -215,7 +329,13 @@ pub(crate) fn add_player_banner(
                     ..Default::default()
                 },
                 Transform::from_xyz(0.0, 0.0, 50.0),
-                Name::new(format!("Banner_{}", state.player_turn)),
+                Name::new(format!("Banner_{}", owner)),
             ));
         });
 }
+
+pub(crate) fn remove_player_banner(commands: &mut Commands, state: &GamesState, position: usize) {
+    commands
+        .entity(state.board_entity[position])
+        .despawn_descendants();
+}
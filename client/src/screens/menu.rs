@@ -10,7 +10,8 @@ use bevy_simple_text_input::{
     TextInput, TextInputPlugin, TextInputSubmitEvent, TextInputSystem, TextInputTextColor,
     TextInputTextFont,
 };
-use shared::action::{Action, PlayerAction};
+use shared::action::{ClientCommand, IdentifyData};
+use shared::list_const::{CAPABILITIES, PROTOCOL_VERSION};
 
 const TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
 
@@ -259,16 +260,18 @@ fn listener(sender: Res<MessageSender>, mut events: EventReader<TextInputSubmitE
         if value.is_empty() {
             return;
         }
+        let identify = IdentifyData {
+            name: value,
+            protocol_version: PROTOCOL_VERSION,
+            capabilities: CAPABILITIES.iter().map(|c| c.to_string()).collect(),
+        };
         let sender = sender.clone();
         let task_pool = AsyncComputeTaskPool::get();
         task_pool
             .spawn(async move {
                 sender
                     .0
-                    .send(PlayerAction {
-                        action_type: Action::Identify,
-                        data: Some(value),
-                    })
+                    .send(ClientCommand::Identify(identify))
                     .await
                     .unwrap();
             })
@@ -1,6 +1,6 @@
-use crate::screens;
-use crate::screens::board::TILE_HEIGHT;
-use bevy::prelude::{Camera2d, Commands, States, Transform};
+use crate::game_state::GamesState;
+use crate::screens::board::board_center;
+use bevy::prelude::{Camera2d, Commands, Res, States, Transform};
 
 pub mod board;
 pub mod menu;
@@ -13,13 +13,8 @@ pub(crate) enum GameStateEnum {
     Game,
 }
 
-pub(crate) fn add_camera(mut commands: Commands) {
-    commands.spawn((
-        Camera2d,
-        Transform::from_xyz(
-            0.0,
-            -TILE_HEIGHT * ((screens::board::GRID_SIZE as f32 / 3.0).round() + 1.0),
-            100.0,
-        ),
-    ));
+// Starts over the board layout, which is empty until the server sends the board
+pub(crate) fn add_camera(mut commands: Commands, games_state: Res<GamesState>) {
+    let (x, y) = board_center(games_state.board.len());
+    commands.spawn((Camera2d, Transform::from_xyz(x, y, 100.0)));
 }
@@ -1,20 +1,22 @@
 use bevy::prelude::*;
-use shared::action::Action;
+use shared::action::ClientCommand;
 
+// Only the commands without a payload can be named alone
 #[allow(dead_code)]
-pub trait ToAction {
-    fn to_action(self) -> Action;
+pub trait ToCommand {
+    fn to_command(self) -> Option<ClientCommand>;
 }
 
-impl ToAction for &str {
-    fn to_action(self) -> Action {
+impl ToCommand for &str {
+    fn to_command(self) -> Option<ClientCommand> {
         match self {
-            "identify" => Action::Identify,
-            "roll" => Action::Roll,
-            "buy_all" => Action::BuyAll,
-            "buy" => Action::BuyProperty,
-            "skip" => Action::SkipBuyProperty,
-            _ => Action::Invalid,
+            "roll" => Some(ClientCommand::Roll),
+            "buy" => Some(ClientCommand::BuyProperty),
+            "skip" => Some(ClientCommand::SkipBuyProperty),
+            "pay_fine" => Some(ClientCommand::PayJailFine),
+            "use_jail_card" => Some(ClientCommand::UseJailCard),
+            "bankrupt" => Some(ClientCommand::DeclareBankruptcy),
+            _ => None,
         }
     }
 }
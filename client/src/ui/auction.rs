@@ -0,0 +1,129 @@
+use crate::communication::MessageSender;
+use crate::game_state::GamesState;
+use bevy::color::Color;
+use bevy::prelude::*;
+use bevy::tasks::AsyncComputeTaskPool;
+use shared::action::ClientCommand;
+
+const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
+const PRESSED_BUTTON: Color = Color::srgb(0.25, 0.25, 0.25);
+
+// Amount added on top of the minimum bid by each button
+const BID_STEPS: [u32; 3] = [0, 50, 100];
+
+#[derive(Component)]
+pub(crate) struct AuctionButton(u32);
+
+#[derive(Component)]
+pub(crate) struct AuctionText;
+
+fn auction_button(
+    step: u32,
+) -> (
+    Button,
+    Node,
+    BorderColor,
+    BorderRadius,
+    BackgroundColor,
+    Name,
+    AuctionButton,
+) {
+    (
+        Button,
+        Node {
+            width: Val::Px(120.0),
+            height: Val::Px(50.0),
+            border: UiRect::all(Val::Px(5.0)),
+            margin: UiRect::horizontal(Val::Px(5.0)),
+            // horizontally center child text
+            justify_content: JustifyContent::Center,
+            // vertically center child text
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BorderColor(Color::BLACK),
+        BorderRadius::MAX,
+        BackgroundColor(NORMAL_BUTTON),
+        Name::new(format!("Bid_{step}")),
+        AuctionButton(step),
+    )
+}
+
+pub(crate) fn spawn_auction_panel(commands: &mut Commands, games_state: &mut GamesState) {
+    games_state.auction_node_id = Some(
+        commands
+            .spawn(Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(40.0),
+                width: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            })
+            .with_children(|parent| {
+                parent.spawn((
+                    Text::new(""),
+                    Node {
+                        margin: UiRect::horizontal(Val::Px(10.0)),
+                        ..default()
+                    },
+                    AuctionText,
+                ));
+                for step in BID_STEPS {
+                    let label = if step == 0 {
+                        "Min bid".to_string()
+                    } else {
+                        format!("+{step}")
+                    };
+                    parent
+                        .spawn(auction_button(step))
+                        .with_child((Text::new(label), TextColor(Color::srgb(0.9, 0.9, 0.9))));
+                }
+            })
+            .id(),
+    );
+}
+
+pub(crate) fn remove_auction_panel(commands: &mut Commands, games_state: &mut GamesState) {
+    if let Some(node_id) = games_state.auction_node_id.take() {
+        commands.entity(node_id).despawn_recursive();
+    }
+}
+
+pub(crate) fn auction_text_system(
+    games_state: Res<GamesState>,
+    mut query: Query<&mut Text, With<AuctionText>>,
+) {
+    for mut text in &mut query {
+        text.0 = format!("Minimum bid: {}", games_state.auction_min_bid);
+    }
+}
+
+pub(crate) fn auction_button_system(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &AuctionButton),
+        Changed<Interaction>,
+    >,
+    games_state: Res<GamesState>,
+    sender: Res<MessageSender>,
+) {
+    for (interaction, mut color, button) in &mut interaction_query {
+        if *interaction != Interaction::Pressed {
+            *color = NORMAL_BUTTON.into();
+            continue;
+        }
+        *color = PRESSED_BUTTON.into();
+        let amount = games_state.auction_min_bid + button.0;
+        let sender = sender.clone();
+        let task_pool = AsyncComputeTaskPool::get();
+        task_pool
+            .spawn(async move {
+                sender
+                    .0
+                    .send(ClientCommand::PlaceBid(amount))
+                    .await
+                    .unwrap();
+            })
+            .detach();
+    }
+}
@@ -0,0 +1,163 @@
+use crate::communication::MessageSender;
+use crate::game_state::GamesState;
+use crate::ui::trade::tile_label;
+use bevy::color::Color;
+use bevy::prelude::*;
+use bevy::tasks::AsyncComputeTaskPool;
+use shared::action::ClientCommand;
+use shared::board::{owns_color_group, Tile};
+use uuid::Uuid;
+
+const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
+const PRESSED_BUTTON: Color = Color::srgb(0.25, 0.25, 0.25);
+const PANEL_COLOR: Color = Color::srgba(0.05, 0.05, 0.05, 0.9);
+const TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+
+#[derive(Component, Clone, Copy)]
+pub(crate) enum BuildingButton {
+    Build(usize),
+    Sell(usize),
+}
+
+// Properties of the groups the player owns whole, the only ones that take buildings
+fn monopoly_tiles(board: &[Tile], owner: Uuid) -> Vec<usize> {
+    board
+        .iter()
+        .enumerate()
+        .filter(|(_, tile)| match tile {
+            Tile::Property { group, .. } => owns_color_group(board, *group, owner),
+            _ => false,
+        })
+        .map(|(position, _)| position)
+        .collect()
+}
+
+fn building_button(action: BuildingButton) -> (Button, Node, BackgroundColor, BuildingButton) {
+    (
+        Button,
+        Node {
+            width: Val::Px(60.0),
+            height: Val::Px(30.0),
+            margin: UiRect::all(Val::Px(2.0)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(NORMAL_BUTTON),
+        action,
+    )
+}
+
+pub(crate) fn spawn_building_picker(commands: &mut Commands, games_state: &mut GamesState) {
+    remove_building_picker(commands, games_state);
+    let board = games_state.board.clone();
+    let tiles = monopoly_tiles(&board, games_state.id);
+    games_state.building_node_id = Some(
+        commands
+            .spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(60.0),
+                    right: Val::Px(10.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(10.0)),
+                    ..default()
+                },
+                BackgroundColor(PANEL_COLOR),
+                Name::new("BuildingPicker"),
+            ))
+            .with_children(|parent| {
+                let title = if tiles.is_empty() {
+                    "No color group to build on (B: close)"
+                } else {
+                    "Buildings (B: close)"
+                };
+                parent.spawn((Text::new(title), TextColor(TEXT_COLOR)));
+                for position in tiles {
+                    let level = match &board[position] {
+                        Tile::Property { level, .. } => level.clone() as usize,
+                        _ => 0,
+                    };
+                    parent
+                        .spawn(Node {
+                            align_items: AlignItems::Center,
+                            ..default()
+                        })
+                        .with_children(|row| {
+                            row.spawn((
+                                Text::new(format!(
+                                    "{} ({level})",
+                                    tile_label(&board, position as u32)
+                                )),
+                                TextColor(TEXT_COLOR),
+                                Node {
+                                    width: Val::Px(220.0),
+                                    ..default()
+                                },
+                            ));
+                            row.spawn(building_button(BuildingButton::Build(position)))
+                                .with_child((Text::new("Build"), TextColor(TEXT_COLOR)));
+                            row.spawn(building_button(BuildingButton::Sell(position)))
+                                .with_child((Text::new("Sell"), TextColor(TEXT_COLOR)));
+                        });
+                }
+            })
+            .id(),
+    );
+}
+
+pub(crate) fn remove_building_picker(commands: &mut Commands, games_state: &mut GamesState) {
+    if let Some(node_id) = games_state.building_node_id.take() {
+        commands.entity(node_id).despawn_recursive();
+    }
+}
+
+// Rebuild the picker so it shows the tiles as they are now
+pub(crate) fn refresh_building_picker(commands: &mut Commands, games_state: &mut GamesState) {
+    if games_state.building_node_id.is_some() {
+        spawn_building_picker(commands, games_state);
+    }
+}
+
+// Open or close the picker (B), it stays usable out of turn to ask for a house in a shortage
+pub(crate) fn open_building_picker(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut commands: Commands,
+    mut games_state: ResMut<GamesState>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::KeyB) || games_state.id.is_nil() {
+        return;
+    }
+    if games_state.building_node_id.is_some() {
+        remove_building_picker(&mut commands, &mut games_state);
+    } else {
+        spawn_building_picker(&mut commands, &mut games_state);
+    }
+}
+
+pub(crate) fn building_button_system(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &BuildingButton),
+        Changed<Interaction>,
+    >,
+    sender: Res<MessageSender>,
+) {
+    for (interaction, mut color, action) in &mut interaction_query {
+        if *interaction != Interaction::Pressed {
+            *color = NORMAL_BUTTON.into();
+            continue;
+        }
+        *color = PRESSED_BUTTON.into();
+        let command = match *action {
+            BuildingButton::Build(position) => ClientCommand::BuildHouse(position),
+            BuildingButton::Sell(position) => ClientCommand::SellHouse(position),
+        };
+        let sender = sender.clone();
+        let task_pool = AsyncComputeTaskPool::get();
+        task_pool
+            .spawn(async move {
+                sender.0.send(command).await.unwrap();
+            })
+            .detach();
+    }
+}
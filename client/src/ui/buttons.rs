@@ -3,7 +3,7 @@ use crate::game_state::GamesState;
 use bevy::color::Color;
 use bevy::prelude::*;
 use bevy::tasks::AsyncComputeTaskPool;
-use shared::action::{Action, PlayerAction};
+use shared::action::ClientCommand;
 
 const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
 const PRESSED_BUTTON: Color = Color::srgb(0.25, 0.25, 0.25);
@@ -98,24 +98,29 @@ pub(crate) fn button_system(
             border_color.0 = Color::WHITE;
             let sender = sender.clone();
             let task_pool = AsyncComputeTaskPool::get();
-            let action = if **text == BUY_BUTTON {
-                Action::BuyProperty
+            let is_buy = **text == BUY_BUTTON;
+            let action = if is_buy {
+                ClientCommand::BuyProperty
             } else {
-                Action::SkipBuyProperty
+                ClientCommand::SkipBuyProperty
             };
             task_pool
                 .spawn(async move {
-                    sender
-                        .0
-                        .send(PlayerAction {
-                            action_type: action,
-                            data: None,
-                        })
-                        .await
-                        .unwrap();
+                    sender.0.send(action).await.unwrap();
                 })
                 .detach();
-            remove_buy_buttons(commands, games_state);
+            // The server refuses a purchase the player can't afford, they still have to skip
+            let can_afford = games_state
+                .players
+                .get(&games_state.id)
+                .is_some_and(|player| {
+                    games_state.board[player.position]
+                        .price()
+                        .is_some_and(|price| player.money >= price)
+                });
+            if !is_buy || can_afford {
+                remove_buy_buttons(commands, games_state);
+            }
             break;
         }
     }
@@ -0,0 +1,207 @@
+use crate::communication::MessageSender;
+use crate::game_state::GamesState;
+use crate::ui::trade::{owned_tiles, tile_label};
+use bevy::color::Color;
+use bevy::prelude::*;
+use bevy::tasks::AsyncComputeTaskPool;
+use shared::action::ClientCommand;
+use shared::board::{PropertyLevel, Tile};
+
+const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
+const PRESSED_BUTTON: Color = Color::srgb(0.25, 0.25, 0.25);
+const TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+
+#[derive(Component)]
+pub(crate) struct BankruptcyButton;
+
+// Ways to raise money from one of the debtor's tiles
+#[derive(Component, Clone, Copy)]
+pub(crate) enum DebtAssetButton {
+    Mortgage(usize),
+    SellHouse(usize),
+}
+
+#[derive(Component)]
+pub(crate) struct DebtText;
+
+pub(crate) fn spawn_debt_panel(commands: &mut Commands, games_state: &mut GamesState) {
+    if games_state.debt_node_id.is_some() {
+        return;
+    }
+    let board = games_state.board.clone();
+    let tiles = owned_tiles(&board, games_state.id);
+    games_state.debt_node_id = Some(
+        commands
+            .spawn(Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(40.0),
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            })
+            .with_children(|parent| {
+                parent.spawn((
+                    Text::new(""),
+                    Node {
+                        margin: UiRect::horizontal(Val::Px(10.0)),
+                        ..default()
+                    },
+                    DebtText,
+                ));
+                parent
+                    .spawn((
+                        Button,
+                        Node {
+                            width: Val::Px(220.0),
+                            height: Val::Px(50.0),
+                            border: UiRect::all(Val::Px(5.0)),
+                            // horizontally center child text
+                            justify_content: JustifyContent::Center,
+                            // vertically center child text
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        BorderColor(Color::BLACK),
+                        BorderRadius::MAX,
+                        BackgroundColor(NORMAL_BUTTON),
+                        Name::new("Declare_bankruptcy"),
+                        BankruptcyButton,
+                    ))
+                    .with_child((Text::new("Declare bankruptcy"), TextColor(TEXT_COLOR)));
+                for position in tiles {
+                    spawn_asset_row(parent, &board, position as usize);
+                }
+            })
+            .id(),
+    );
+}
+
+fn asset_button(action: DebtAssetButton) -> (Button, Node, BackgroundColor, DebtAssetButton) {
+    (
+        Button,
+        Node {
+            width: Val::Px(110.0),
+            height: Val::Px(30.0),
+            margin: UiRect::all(Val::Px(2.0)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(NORMAL_BUTTON),
+        action,
+    )
+}
+
+// One of the debtor's tiles, with the ways it can still raise money
+fn spawn_asset_row(parent: &mut ChildBuilder, board: &[Tile], position: usize) {
+    let has_buildings =
+        matches!(&board[position], Tile::Property { level, .. } if *level != PropertyLevel::None);
+    parent
+        .spawn(Node {
+            align_items: AlignItems::Center,
+            ..default()
+        })
+        .with_children(|row| {
+            row.spawn((
+                Text::new(tile_label(board, position as u32)),
+                TextColor(TEXT_COLOR),
+                Node {
+                    width: Val::Px(220.0),
+                    ..default()
+                },
+            ));
+            if !board[position].is_mortgaged() {
+                row.spawn(asset_button(DebtAssetButton::Mortgage(position)))
+                    .with_child((Text::new("Mortgage"), TextColor(TEXT_COLOR)));
+            }
+            if has_buildings {
+                row.spawn(asset_button(DebtAssetButton::SellHouse(position)))
+                    .with_child((Text::new("Sell house"), TextColor(TEXT_COLOR)));
+            }
+        });
+}
+
+// Rebuild the panel so it shows the tiles as they are now
+pub(crate) fn refresh_debt_panel(commands: &mut Commands, games_state: &mut GamesState) {
+    if games_state.debt_node_id.is_some() {
+        remove_debt_panel(commands, games_state);
+        spawn_debt_panel(commands, games_state);
+    }
+}
+
+pub(crate) fn remove_debt_panel(commands: &mut Commands, games_state: &mut GamesState) {
+    if let Some(node_id) = games_state.debt_node_id.take() {
+        commands.entity(node_id).despawn_recursive();
+    }
+}
+
+pub(crate) fn debt_text_system(
+    games_state: Res<GamesState>,
+    mut query: Query<&mut Text, With<DebtText>>,
+) {
+    let owed: u32 = games_state
+        .debts
+        .iter()
+        .filter(|debt| debt.debtor == games_state.id)
+        .map(|debt| debt.amount)
+        .sum();
+    for mut text in &mut query {
+        text.0 = format!("Raise {owed} to pay your debts (mortgage or sell buildings below)");
+    }
+}
+
+pub(crate) fn bankruptcy_button_system(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<BankruptcyButton>),
+    >,
+    sender: Res<MessageSender>,
+) {
+    for (interaction, mut color) in &mut interaction_query {
+        if *interaction != Interaction::Pressed {
+            *color = NORMAL_BUTTON.into();
+            continue;
+        }
+        *color = PRESSED_BUTTON.into();
+        let sender = sender.clone();
+        let task_pool = AsyncComputeTaskPool::get();
+        task_pool
+            .spawn(async move {
+                sender
+                    .0
+                    .send(ClientCommand::DeclareBankruptcy)
+                    .await
+                    .unwrap();
+            })
+            .detach();
+    }
+}
+
+// The debtor raises money out of turn as well, the server only accepts it while they owe
+pub(crate) fn debt_asset_button_system(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &DebtAssetButton),
+        Changed<Interaction>,
+    >,
+    sender: Res<MessageSender>,
+) {
+    for (interaction, mut color, action) in &mut interaction_query {
+        if *interaction != Interaction::Pressed {
+            *color = NORMAL_BUTTON.into();
+            continue;
+        }
+        *color = PRESSED_BUTTON.into();
+        let command = match *action {
+            DebtAssetButton::Mortgage(position) => ClientCommand::Mortgage(position),
+            DebtAssetButton::SellHouse(position) => ClientCommand::SellHouse(position),
+        };
+        let sender = sender.clone();
+        let task_pool = AsyncComputeTaskPool::get();
+        task_pool
+            .spawn(async move {
+                sender.0.send(command).await.unwrap();
+            })
+            .detach();
+    }
+}
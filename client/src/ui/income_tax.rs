@@ -0,0 +1,110 @@
+use crate::communication::MessageSender;
+use crate::game_state::GamesState;
+use bevy::color::Color;
+use bevy::prelude::*;
+use bevy::tasks::AsyncComputeTaskPool;
+use shared::action::{ClientCommand, IncomeTaxChoice, IncomeTaxData};
+
+const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
+const PRESSED_BUTTON: Color = Color::srgb(0.25, 0.25, 0.25);
+
+#[derive(Component)]
+pub(crate) struct IncomeTaxButton(IncomeTaxChoice);
+
+fn income_tax_button(
+    choice: IncomeTaxChoice,
+) -> (
+    Button,
+    Node,
+    BorderColor,
+    BorderRadius,
+    BackgroundColor,
+    IncomeTaxButton,
+) {
+    (
+        Button,
+        Node {
+            width: Val::Px(220.0),
+            height: Val::Px(50.0),
+            border: UiRect::all(Val::Px(5.0)),
+            margin: UiRect::horizontal(Val::Px(5.0)),
+            // horizontally center child text
+            justify_content: JustifyContent::Center,
+            // vertically center child text
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BorderColor(Color::BLACK),
+        BorderRadius::MAX,
+        BackgroundColor(NORMAL_BUTTON),
+        IncomeTaxButton(choice),
+    )
+}
+
+pub(crate) fn spawn_income_tax_panel(
+    commands: &mut Commands,
+    games_state: &mut GamesState,
+    data: &IncomeTaxData,
+) {
+    remove_income_tax_panel(commands, games_state);
+    let choices = [
+        (IncomeTaxChoice::Flat, format!("Pay {}", data.flat)),
+        (
+            IncomeTaxChoice::Percent,
+            format!("Pay {}% of net worth", data.rate),
+        ),
+    ];
+    games_state.income_tax_node_id = Some(
+        commands
+            .spawn(Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(40.0),
+                width: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            })
+            .with_children(|parent| {
+                for (choice, label) in choices {
+                    parent
+                        .spawn(income_tax_button(choice))
+                        .with_child((Text::new(label), TextColor(Color::srgb(0.9, 0.9, 0.9))));
+                }
+            })
+            .id(),
+    );
+}
+
+pub(crate) fn remove_income_tax_panel(commands: &mut Commands, games_state: &mut GamesState) {
+    if let Some(node_id) = games_state.income_tax_node_id.take() {
+        commands.entity(node_id).despawn_recursive();
+    }
+}
+
+pub(crate) fn income_tax_button_system(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &IncomeTaxButton),
+        Changed<Interaction>,
+    >,
+    sender: Res<MessageSender>,
+) {
+    for (interaction, mut color, button) in &mut interaction_query {
+        if *interaction != Interaction::Pressed {
+            *color = NORMAL_BUTTON.into();
+            continue;
+        }
+        *color = PRESSED_BUTTON.into();
+        let choice = button.0;
+        let sender = sender.clone();
+        let task_pool = AsyncComputeTaskPool::get();
+        task_pool
+            .spawn(async move {
+                sender
+                    .0
+                    .send(ClientCommand::ChooseIncomeTax(choice))
+                    .await
+                    .unwrap();
+            })
+            .detach();
+    }
+}
@@ -1,4 +1,10 @@
+pub mod auction;
+pub mod buildings;
 pub mod buttons;
+pub mod debt;
+pub mod income_tax;
 pub mod money;
 pub mod name;
+pub mod speed_die;
 pub mod toast;
+pub mod trade;
@@ -1,5 +1,6 @@
 use crate::game_state::GamesState;
 use bevy::prelude::*;
+use shared::board::net_worth;
 
 #[derive(Component)]
 pub(crate) struct MoneyText;
@@ -9,6 +10,10 @@ pub(crate) fn scoreboard_system(
     mut display: Single<&mut Text, With<MoneyText>>,
 ) {
     if let Some(player) = game.players.get(&game.id) {
-        display.0 = format!("Money: {}", player.money);
+        let worth = net_worth(&game.board, game.id, player.money);
+        display.0 = format!("Money: {}  Net worth: {}", player.money, worth);
+        if game.rules.free_parking_jackpot {
+            display.0 += &format!("  Jackpot: {}", game.jackpot);
+        }
     }
 }
@@ -0,0 +1,173 @@
+use crate::communication::MessageSender;
+use crate::game_state::GamesState;
+use bevy::color::Color;
+use bevy::prelude::*;
+use bevy::tasks::AsyncComputeTaskPool;
+use shared::action::ClientCommand;
+
+const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
+const PRESSED_BUTTON: Color = Color::srgb(0.25, 0.25, 0.25);
+
+#[derive(Component, Clone, Copy)]
+pub(crate) enum SpeedDieButton {
+    Bus(u8),
+    Previous,
+    Next,
+    Teleport,
+}
+
+#[derive(Component)]
+pub(crate) struct TeleportText;
+
+fn speed_die_button(
+    button: SpeedDieButton,
+) -> (
+    Button,
+    Node,
+    BorderColor,
+    BorderRadius,
+    BackgroundColor,
+    SpeedDieButton,
+) {
+    (
+        Button,
+        Node {
+            width: Val::Px(if matches!(button, SpeedDieButton::Teleport) {
+                220.0
+            } else {
+                120.0
+            }),
+            height: Val::Px(50.0),
+            border: UiRect::all(Val::Px(5.0)),
+            margin: UiRect::horizontal(Val::Px(5.0)),
+            // horizontally center child text
+            justify_content: JustifyContent::Center,
+            // vertically center child text
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BorderColor(Color::BLACK),
+        BorderRadius::MAX,
+        BackgroundColor(NORMAL_BUTTON),
+        button,
+    )
+}
+
+fn spawn_speed_die_panel(
+    commands: &mut Commands,
+    games_state: &mut GamesState,
+    buttons: Vec<(SpeedDieButton, String)>,
+) {
+    remove_speed_die_panel(commands, games_state);
+    games_state.speed_die_node_id = Some(
+        commands
+            .spawn(Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(40.0),
+                width: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            })
+            .with_children(|parent| {
+                for (button, label) in buttons {
+                    let mut entity = parent.spawn(speed_die_button(button));
+                    if matches!(button, SpeedDieButton::Teleport) {
+                        entity.with_child((
+                            Text::new(label),
+                            TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                            TeleportText,
+                        ));
+                    } else {
+                        entity
+                            .with_child((Text::new(label), TextColor(Color::srgb(0.9, 0.9, 0.9))));
+                    }
+                }
+            })
+            .id(),
+    );
+}
+
+// The bus moves by either white die or by both
+pub(crate) fn spawn_bus_panel(
+    commands: &mut Commands,
+    games_state: &mut GamesState,
+    dice1: u8,
+    dice2: u8,
+) {
+    let mut steps = vec![dice1, dice2, dice1 + dice2];
+    steps.dedup();
+    let buttons = steps
+        .into_iter()
+        .map(|steps| (SpeedDieButton::Bus(steps), format!("Move {steps}")))
+        .collect();
+    spawn_speed_die_panel(commands, games_state, buttons);
+}
+
+// A triple picks any tile of the board, starting from the player's own
+pub(crate) fn spawn_teleport_panel(commands: &mut Commands, games_state: &mut GamesState) {
+    games_state.teleport_target = games_state
+        .players
+        .get(&games_state.id)
+        .map(|player| player.position)
+        .unwrap_or(0);
+    let buttons = vec![
+        (SpeedDieButton::Previous, "<".to_owned()),
+        (SpeedDieButton::Teleport, String::new()),
+        (SpeedDieButton::Next, ">".to_owned()),
+    ];
+    spawn_speed_die_panel(commands, games_state, buttons);
+}
+
+pub(crate) fn remove_speed_die_panel(commands: &mut Commands, games_state: &mut GamesState) {
+    if let Some(node_id) = games_state.speed_die_node_id.take() {
+        commands.entity(node_id).despawn_recursive();
+    }
+}
+
+pub(crate) fn teleport_text_system(
+    games_state: Res<GamesState>,
+    mut query: Query<&mut Text, With<TeleportText>>,
+) {
+    for mut text in &mut query {
+        text.0 = format!("Teleport to {}", games_state.teleport_target);
+    }
+}
+
+pub(crate) fn speed_die_button_system(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &SpeedDieButton),
+        Changed<Interaction>,
+    >,
+    mut games_state: ResMut<GamesState>,
+    sender: Res<MessageSender>,
+) {
+    for (interaction, mut color, button) in &mut interaction_query {
+        if *interaction != Interaction::Pressed {
+            *color = NORMAL_BUTTON.into();
+            continue;
+        }
+        *color = PRESSED_BUTTON.into();
+        let board_size = games_state.board.len();
+        let command = match button {
+            SpeedDieButton::Previous => {
+                games_state.teleport_target =
+                    (games_state.teleport_target + board_size - 1) % board_size;
+                continue;
+            }
+            SpeedDieButton::Next => {
+                games_state.teleport_target = (games_state.teleport_target + 1) % board_size;
+                continue;
+            }
+            SpeedDieButton::Bus(steps) => ClientCommand::TakeBus(*steps),
+            SpeedDieButton::Teleport => ClientCommand::Teleport(games_state.teleport_target),
+        };
+        let sender = sender.clone();
+        let task_pool = AsyncComputeTaskPool::get();
+        task_pool
+            .spawn(async move {
+                sender.0.send(command).await.unwrap();
+            })
+            .detach();
+    }
+}
@@ -0,0 +1,485 @@
+use crate::communication::MessageSender;
+use crate::game_state::GamesState;
+use bevy::color::Color;
+use bevy::prelude::*;
+use bevy::tasks::AsyncComputeTaskPool;
+use shared::action::{ClientCommand, TradeAssets, TradeOffer};
+use shared::board::Tile;
+use uuid::Uuid;
+
+const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
+const SELECTED_BUTTON: Color = Color::srgb(0.35, 0.75, 0.35);
+const PANEL_COLOR: Color = Color::srgba(0.05, 0.05, 0.05, 0.9);
+const TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const MONEY_STEP: u32 = 50;
+
+// Trade being prepared in the dialog
+#[derive(Debug, Clone)]
+pub(crate) struct TradeDraft {
+    pub(crate) partner: Uuid,
+    pub(crate) offered: TradeAssets,
+    pub(crate) requested: TradeAssets,
+    // Id of the offer this draft answers, if it is a counter-offer
+    pub(crate) counter_of: Option<Uuid>,
+}
+
+#[derive(Component, Clone, Copy)]
+pub(crate) enum TradeButton {
+    ToggleOffered(u32),
+    ToggleRequested(u32),
+    ToggleOfferedJailCard,
+    ToggleRequestedJailCard,
+    OfferMore,
+    OfferLess,
+    RequestMore,
+    RequestLess,
+    Propose,
+    Close,
+    Accept,
+    Reject,
+    Counter,
+}
+
+#[derive(Component)]
+pub(crate) struct TradeMoneyText {
+    offered: bool,
+}
+
+pub(crate) fn tile_label(board: &[Tile], position: u32) -> String {
+    match &board[position as usize] {
+        Tile::Property { name, .. } => name.clone(),
+        Tile::Railroad { .. } => format!("Railroad ({position})"),
+        Tile::Utility { .. } => format!("Utility ({position})"),
+        _ => format!("Tile {position}"),
+    }
+}
+
+fn assets_label(board: &[Tile], assets: &TradeAssets) -> String {
+    let mut parts: Vec<String> = assets
+        .tiles
+        .iter()
+        .map(|position| tile_label(board, *position))
+        .collect();
+    if assets.money > 0 {
+        parts.push(format!("{} money", assets.money));
+    }
+    if assets.jail_cards > 0 {
+        parts.push(format!(
+            "{} Get Out of Jail Free card(s)",
+            assets.jail_cards
+        ));
+    }
+    if parts.is_empty() {
+        "nothing".to_string()
+    } else {
+        parts.join(", ")
+    }
+}
+
+fn trade_button(action: TradeButton, width: f32) -> (Button, Node, BackgroundColor, TradeButton) {
+    (
+        Button,
+        Node {
+            width: Val::Px(width),
+            height: Val::Px(30.0),
+            margin: UiRect::all(Val::Px(2.0)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(NORMAL_BUTTON),
+        action,
+    )
+}
+
+pub(crate) fn owned_tiles(board: &[Tile], owner: Uuid) -> Vec<u32> {
+    board
+        .iter()
+        .enumerate()
+        .filter(|(_, tile)| tile.owner() == Some(owner))
+        .map(|(position, _)| position as u32)
+        .collect()
+}
+
+fn spawn_assets_column(
+    parent: &mut ChildBuilder,
+    title: String,
+    board: &[Tile],
+    tiles: Vec<u32>,
+    jail_cards: u32,
+    offered: bool,
+) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Column,
+            margin: UiRect::all(Val::Px(10.0)),
+            ..default()
+        })
+        .with_children(|column| {
+            column.spawn((Text::new(title), TextColor(TEXT_COLOR)));
+            for position in tiles {
+                let toggle = if offered {
+                    TradeButton::ToggleOffered(position)
+                } else {
+                    TradeButton::ToggleRequested(position)
+                };
+                column.spawn(trade_button(toggle, 220.0)).with_child((
+                    Text::new(tile_label(board, position)),
+                    TextColor(TEXT_COLOR),
+                ));
+            }
+            if jail_cards > 0 {
+                let toggle = if offered {
+                    TradeButton::ToggleOfferedJailCard
+                } else {
+                    TradeButton::ToggleRequestedJailCard
+                };
+                column
+                    .spawn(trade_button(toggle, 220.0))
+                    .with_child((Text::new("Get Out of Jail Free"), TextColor(TEXT_COLOR)));
+            }
+            column
+                .spawn(Node {
+                    align_items: AlignItems::Center,
+                    ..default()
+                })
+                .with_children(|row| {
+                    let (less, more) = if offered {
+                        (TradeButton::OfferLess, TradeButton::OfferMore)
+                    } else {
+                        (TradeButton::RequestLess, TradeButton::RequestMore)
+                    };
+                    row.spawn(trade_button(less, 40.0))
+                        .with_child((Text::new("-"), TextColor(TEXT_COLOR)));
+                    row.spawn((
+                        Text::new(""),
+                        TextColor(TEXT_COLOR),
+                        TradeMoneyText { offered },
+                    ));
+                    row.spawn(trade_button(more, 40.0))
+                        .with_child((Text::new("+"), TextColor(TEXT_COLOR)));
+                });
+        });
+}
+
+pub(crate) fn spawn_trade_dialog(
+    commands: &mut Commands,
+    games_state: &mut GamesState,
+    draft: TradeDraft,
+) {
+    remove_trade_dialog(commands, games_state);
+    let partner_name = games_state
+        .players
+        .get(&draft.partner)
+        .map(|player| player.name.clone())
+        .unwrap_or_default();
+    let my_tiles = owned_tiles(&games_state.board, games_state.id);
+    let partner_tiles = owned_tiles(&games_state.board, draft.partner);
+    let jail_cards = |id: Uuid| games_state.players.get(&id).map_or(0, |p| p.jail_cards);
+    let (my_jail_cards, partner_jail_cards) =
+        (jail_cards(games_state.id), jail_cards(draft.partner));
+    let board = games_state.board.clone();
+    games_state.trade_node_id = Some(
+        commands
+            .spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(60.0),
+                    left: Val::Px(10.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(10.0)),
+                    ..default()
+                },
+                BackgroundColor(PANEL_COLOR),
+                Name::new("TradeDialog"),
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Text::new(format!("Trade with {partner_name} (T: next player)")),
+                    TextColor(TEXT_COLOR),
+                ));
+                parent.spawn(Node::default()).with_children(|columns| {
+                    spawn_assets_column(
+                        columns,
+                        "You give".to_string(),
+                        &board,
+                        my_tiles,
+                        my_jail_cards,
+                        true,
+                    );
+                    spawn_assets_column(
+                        columns,
+                        "You get".to_string(),
+                        &board,
+                        partner_tiles,
+                        partner_jail_cards,
+                        false,
+                    );
+                });
+                parent.spawn(Node::default()).with_children(|row| {
+                    row.spawn(trade_button(TradeButton::Propose, 120.0))
+                        .with_child((Text::new("Propose"), TextColor(TEXT_COLOR)));
+                    row.spawn(trade_button(TradeButton::Close, 120.0))
+                        .with_child((Text::new("Close"), TextColor(TEXT_COLOR)));
+                });
+            })
+            .id(),
+    );
+    games_state.trade_draft = Some(draft);
+}
+
+pub(crate) fn remove_trade_dialog(commands: &mut Commands, games_state: &mut GamesState) {
+    if let Some(node_id) = games_state.trade_node_id.take() {
+        commands.entity(node_id).despawn_recursive();
+    }
+    games_state.trade_draft = None;
+}
+
+pub(crate) fn spawn_trade_offer(
+    commands: &mut Commands,
+    games_state: &mut GamesState,
+    offer: TradeOffer,
+) {
+    remove_trade_offer(commands, games_state);
+    let from_name = games_state
+        .players
+        .get(&offer.from)
+        .map(|player| player.name.clone())
+        .unwrap_or_default();
+    let summary = format!(
+        "{} offers {} for {}",
+        from_name,
+        assets_label(&games_state.board, &offer.offered),
+        assets_label(&games_state.board, &offer.requested)
+    );
+    games_state.trade_offer_node_id = Some(
+        commands
+            .spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(60.0),
+                    right: Val::Px(10.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(10.0)),
+                    ..default()
+                },
+                BackgroundColor(PANEL_COLOR),
+                Name::new("TradeOffer"),
+            ))
+            .with_children(|parent| {
+                parent.spawn((Text::new(summary), TextColor(TEXT_COLOR)));
+                parent.spawn(Node::default()).with_children(|row| {
+                    for (button, label) in [
+                        (TradeButton::Accept, "Accept"),
+                        (TradeButton::Reject, "Reject"),
+                        (TradeButton::Counter, "Counter"),
+                    ] {
+                        row.spawn(trade_button(button, 100.0))
+                            .with_child((Text::new(label), TextColor(TEXT_COLOR)));
+                    }
+                });
+            })
+            .id(),
+    );
+    games_state.trade_offer = Some(offer);
+}
+
+pub(crate) fn remove_trade_offer(commands: &mut Commands, games_state: &mut GamesState) {
+    if let Some(node_id) = games_state.trade_offer_node_id.take() {
+        commands.entity(node_id).despawn_recursive();
+    }
+    games_state.trade_offer = None;
+}
+
+// Open the trade dialog (T), pressing it again moves on to the next player
+pub(crate) fn open_trade_dialog(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut commands: Commands,
+    mut games_state: ResMut<GamesState>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::KeyT) || games_state.id.is_nil() {
+        return;
+    }
+    let mut partners: Vec<(usize, Uuid)> = games_state
+        .players
+        .iter()
+        .filter(|(id, player)| **id != games_state.id && !player.is_bankrupt)
+        .map(|(id, player)| (player.player_number, *id))
+        .collect();
+    partners.sort();
+    if partners.is_empty() {
+        return;
+    }
+    let next = match &games_state.trade_draft {
+        Some(draft) => partners
+            .iter()
+            .position(|(_, id)| *id == draft.partner)
+            .map_or(0, |index| (index + 1) % partners.len()),
+        None => 0,
+    };
+    let draft = TradeDraft {
+        partner: partners[next].1,
+        offered: TradeAssets::default(),
+        requested: TradeAssets::default(),
+        counter_of: None,
+    };
+    spawn_trade_dialog(&mut commands, &mut games_state, draft);
+}
+
+fn toggle(tiles: &mut Vec<u32>, position: u32) {
+    if let Some(index) = tiles.iter().position(|tile| *tile == position) {
+        tiles.remove(index);
+    } else {
+        tiles.push(position);
+    }
+}
+
+fn send(sender: &MessageSender, command: ClientCommand) {
+    let sender = sender.clone();
+    let task_pool = AsyncComputeTaskPool::get();
+    task_pool
+        .spawn(async move {
+            sender.0.send(command).await.unwrap();
+        })
+        .detach();
+}
+
+pub(crate) fn trade_button_system(
+    interaction_query: Query<(&Interaction, &TradeButton), Changed<Interaction>>,
+    mut commands: Commands,
+    mut games_state: ResMut<GamesState>,
+    sender: Res<MessageSender>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let my_id = games_state.id;
+        match *button {
+            TradeButton::ToggleOffered(position) => {
+                if let Some(draft) = games_state.trade_draft.as_mut() {
+                    toggle(&mut draft.offered.tiles, position);
+                }
+            }
+            TradeButton::ToggleRequested(position) => {
+                if let Some(draft) = games_state.trade_draft.as_mut() {
+                    toggle(&mut draft.requested.tiles, position);
+                }
+            }
+            // A single card is traded at a time
+            TradeButton::ToggleOfferedJailCard => {
+                if let Some(draft) = games_state.trade_draft.as_mut() {
+                    draft.offered.jail_cards = 1 - draft.offered.jail_cards.min(1);
+                }
+            }
+            TradeButton::ToggleRequestedJailCard => {
+                if let Some(draft) = games_state.trade_draft.as_mut() {
+                    draft.requested.jail_cards = 1 - draft.requested.jail_cards.min(1);
+                }
+            }
+            TradeButton::OfferMore | TradeButton::OfferLess => {
+                let money = games_state.players.get(&my_id).map_or(0, |p| p.money);
+                if let Some(draft) = games_state.trade_draft.as_mut() {
+                    draft.offered.money = if matches!(button, TradeButton::OfferMore) {
+                        (draft.offered.money + MONEY_STEP).min(money)
+                    } else {
+                        draft.offered.money.saturating_sub(MONEY_STEP)
+                    };
+                }
+            }
+            TradeButton::RequestMore | TradeButton::RequestLess => {
+                let partner = games_state.trade_draft.as_ref().map(|draft| draft.partner);
+                let money = partner
+                    .and_then(|partner| games_state.players.get(&partner))
+                    .map_or(0, |p| p.money);
+                if let Some(draft) = games_state.trade_draft.as_mut() {
+                    draft.requested.money = if matches!(button, TradeButton::RequestMore) {
+                        (draft.requested.money + MONEY_STEP).min(money)
+                    } else {
+                        draft.requested.money.saturating_sub(MONEY_STEP)
+                    };
+                }
+            }
+            TradeButton::Propose => {
+                if let Some(draft) = games_state.trade_draft.clone() {
+                    let offer = TradeOffer {
+                        id: draft.counter_of.unwrap_or(Uuid::nil()),
+                        from: my_id,
+                        to: draft.partner,
+                        offered: draft.offered,
+                        requested: draft.requested,
+                    };
+                    let command = if draft.counter_of.is_some() {
+                        ClientCommand::CounterTrade(offer)
+                    } else {
+                        ClientCommand::ProposeTrade(offer)
+                    };
+                    send(&sender, command);
+                }
+                remove_trade_dialog(&mut commands, &mut games_state);
+            }
+            TradeButton::Close => {
+                remove_trade_dialog(&mut commands, &mut games_state);
+            }
+            TradeButton::Accept | TradeButton::Reject => {
+                if let Some(offer) = &games_state.trade_offer {
+                    let command = if matches!(button, TradeButton::Accept) {
+                        ClientCommand::AcceptTrade(offer.id)
+                    } else {
+                        ClientCommand::RejectTrade(offer.id)
+                    };
+                    send(&sender, command);
+                }
+                remove_trade_offer(&mut commands, &mut games_state);
+            }
+            TradeButton::Counter => {
+                if let Some(offer) = games_state.trade_offer.clone() {
+                    // Start from the received offer seen from our side
+                    let draft = TradeDraft {
+                        partner: offer.from,
+                        offered: offer.requested,
+                        requested: offer.offered,
+                        counter_of: Some(offer.id),
+                    };
+                    remove_trade_offer(&mut commands, &mut games_state);
+                    spawn_trade_dialog(&mut commands, &mut games_state, draft);
+                }
+            }
+        }
+        break;
+    }
+}
+
+// Highlight the selected tiles and show the amounts of the draft
+pub(crate) fn trade_dialog_system(
+    games_state: Res<GamesState>,
+    mut buttons: Query<(&TradeButton, &mut BackgroundColor)>,
+    mut texts: Query<(&TradeMoneyText, &mut Text)>,
+) {
+    let Some(draft) = &games_state.trade_draft else {
+        return;
+    };
+    for (button, mut color) in &mut buttons {
+        let selected = match *button {
+            TradeButton::ToggleOffered(position) => draft.offered.tiles.contains(&position),
+            TradeButton::ToggleRequested(position) => draft.requested.tiles.contains(&position),
+            TradeButton::ToggleOfferedJailCard => draft.offered.jail_cards > 0,
+            TradeButton::ToggleRequestedJailCard => draft.requested.jail_cards > 0,
+            _ => continue,
+        };
+        *color = if selected {
+            SELECTED_BUTTON
+        } else {
+            NORMAL_BUTTON
+        }
+        .into();
+    }
+    for (money_text, mut text) in &mut texts {
+        let amount = if money_text.offered {
+            draft.offered.money
+        } else {
+            draft.requested.money
+        };
+        text.0 = format!(" {amount} money ");
+    }
+}
@@ -1,175 +1,390 @@
+use crate::auction::auction_unsold_tile;
+use crate::cards::{draw_card, return_jail_card};
 use crate::communication::send_to_all_players;
-use crate::game_state::{Game, Player};
-use serde_json::to_string;
-use shared::action::Action::PayRent;
+use crate::debt::{charge, Payment};
+use crate::dice::{DiceRoll, PendingMove};
+use crate::error::GameError;
+use crate::game_state::{Game, TurnPhase};
+use crate::jackpot::collect_pot;
+use crate::jail::{release, send_jail_exit};
+use crate::tax::{ask_income_tax, pay_tax};
 use shared::action::{
-    Action, BuyPropertyData, DiceRollData, PayRentData, PlayerGoTileData, PlayerPayTaxData,
+    BankruptData, BuyPropertyData, DiceRollData, ErrorCode, PayRentData, PlayerGoTileData,
+    ServerEvent, SpeedDieFace,
 };
 use shared::board::Tile::{
     Chance, FreeParking, Go, GoToJail, Jail, LuxuryTax, Property, Railroad, Tax, Utility,
 };
+use shared::board::{owns_color_group, ColorGroup, PropertyLevel};
+use shared::cards::CardDeck;
+use std::fmt;
 use uuid::Uuid;
 
+#[derive(Debug, PartialEq)]
+pub(crate) enum BuyError {
+    NotEnoughMoney,
+}
+
+impl fmt::Display for BuyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            BuyError::NotEnoughMoney => "Not enough money to buy the property",
+        };
+        write!(f, "{message}")
+    }
+}
+
+impl GameError for BuyError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            BuyError::NotEnoughMoney => ErrorCode::InsufficientFunds,
+        }
+    }
+}
+
 pub(crate) async fn roll_dice(game: &mut Game, uuid: &Uuid) -> (u8, u8) {
     log::debug!("Player {uuid} rolled the dice");
-    // Generate random number between 2 and 12
-    let roll1 = rand::random::<u8>() % 6 + 1;
-    let roll2 = rand::random::<u8>() % 6 + 1;
-    let roll = roll1 + roll2;
+    game.phase = TurnPhase::TurnEnd;
+    let mut dice = game.dice.roll();
+    // Forced rolls are used up, the dice of the rules take over
+    #[cfg(feature = "debug-commands")]
+    if matches!(&game.dice, crate::dice::DiceModel::Loaded(rolls) if rolls.is_empty()) {
+        game.dice = crate::dice::DiceModel::from_rules(&game.rules);
+    }
+    let (roll1, roll2) = (dice.dice1, dice.dice2);
     if game.players[game.player_turn].is_in_jail {
         log::debug!("Player {uuid} is in jail");
-        if roll1 == roll2 {
-            game.players[game.player_turn].is_in_jail = false;
-            game.players[game.player_turn].jail_turns = 0;
+        // Only the white dice count to leave jail
+        dice.speed_die = None;
+        if dice.is_double() {
+            release(game, *uuid);
             log::debug!("Player {uuid} rolled doubles and is out of jail");
             send_to_all_players(
                 &game.players,
-                Action::FreeFromJail,
-                Some(game.players[game.player_turn].id.to_string()),
+                ServerEvent::FreeFromJail(game.players[game.player_turn].id),
             )
             .await;
         } else {
             game.players[game.player_turn].jail_turns -= 1;
-            if game.players[game.player_turn].jail_turns == 0 {
-                game.players[game.player_turn].is_in_jail = false;
-                log::debug!("Player {uuid} is out of jail");
-            } else {
+            if game.players[game.player_turn].jail_turns > 0 {
                 log::debug!("Player {uuid} is still in jail");
+                game.advance_turn().await;
+                return (roll1, roll2);
             }
+            // Third failed attempt: the fine is due and the player moves with this roll
+            log::debug!("Player {uuid} must pay the fine to leave jail");
+            release(game, *uuid);
+            let fine = game.rules.jail_fine;
+            match charge(game, *uuid, None, fine).await {
+                Payment::Paid => {
+                    send_jail_exit(game, ServerEvent::PayJailFine, *uuid, fine, None).await;
+                }
+                // The move waits for the player to raise the fine
+                Payment::Debt => {
+                    send_to_all_players(&game.players, ServerEvent::FreeFromJail(*uuid)).await;
+                    send_roll(game, &dice, false).await;
+                    game.last_roll = dice.total();
+                    game.pending_move = Some(PendingMove::JailFine {
+                        steps: dice.total(),
+                    });
+                    return (roll1, roll2);
+                }
+                Payment::Bankrupt => {
+                    game.advance_turn().await;
+                    return (roll1, roll2);
+                }
+            }
+        }
+    } else if dice.is_double() && !dice.is_triple() {
+        // A triple picks a tile instead, it doesn't count as doubles
+        game.players[game.player_turn].doubles_count += 1;
+        if game.players[game.player_turn].doubles_count == 3 {
+            log::debug!("Player {uuid} rolled doubles three times in a row");
+            send_roll(game, &dice, false).await;
+            send_player_to_jail(game).await;
             game.advance_turn().await;
             return (roll1, roll2);
         }
+    } else {
+        game.players[game.player_turn].doubles_count = 0;
     }
-    game.players[game.player_turn].position =
-        (game.players[game.player_turn].position + roll as usize) % game.board.len();
-    let current_position = game.players[game.player_turn].position;
-    log::debug!("Player {uuid} moved to position {current_position}");
-    log::debug!("Tile: {:?}", game.board[current_position]);
-    send_to_all_players(
-        &game.players,
-        Action::Roll,
-        Some(
-            to_string(&DiceRollData {
+    // Leaving jail with doubles doesn't give another roll
+    let extra_roll = game.players[game.player_turn].doubles_count > 0;
+    send_roll(game, &dice, extra_roll).await;
+    game.last_roll = dice.total();
+    if dice.is_triple() {
+        log::debug!("Player {uuid} rolled a triple and picks a tile");
+        game.pending_move = Some(PendingMove::Teleport);
+        game.phase = TurnPhase::AwaitingMoveChoice;
+        return (roll1, roll2);
+    }
+    match dice.speed_die {
+        Some(SpeedDieFace::Bus) => {
+            log::debug!("Player {uuid} rolled the bus and picks a die");
+            game.pending_move = Some(PendingMove::Bus {
                 dice1: roll1,
                 dice2: roll2,
-            })
-            .unwrap(),
-        ),
-    )
-    .await;
+            });
+            game.phase = TurnPhase::AwaitingMoveChoice;
+            return (roll1, roll2);
+        }
+        // Mr. Monopoly moves the player again once this tile is resolved
+        Some(SpeedDieFace::MrMonopoly) => game.mr_monopoly = true,
+        _ => {}
+    }
+    move_forward(game, uuid, dice.total() as usize, dice.total()).await;
+    (roll1, roll2)
+}
+
+async fn send_roll(game: &Game, dice: &DiceRoll, extra_roll: bool) {
     send_to_all_players(
         &game.players,
-        Action::Move,
-        Some(current_position.to_string()),
+        ServerEvent::Roll(DiceRollData {
+            dice1: dice.dice1,
+            dice2: dice.dice2,
+            extra_roll,
+            speed_die: dice.speed_die,
+        }),
     )
     .await;
+}
+
+// Move the current player, paying the Go tiles passed on the way, and resolve the tile
+pub(crate) async fn move_forward(game: &mut Game, uuid: &Uuid, steps: usize, roll: u8) {
+    let start = game.players[game.player_turn].position;
+    let position = (start + steps) % game.board.len();
+    game.players[game.player_turn].position = position;
+    log::debug!("Player {uuid} moved to position {position}");
+    pass_go_tiles(game, start, steps).await;
+    send_to_all_players(&game.players, ServerEvent::Move(position)).await;
+    Box::pin(land_on_tile(game, uuid, roll)).await;
+}
+
+fn go_salary(game: &Game, tile_amount: u32) -> u32 {
+    game.rules.go_salary.unwrap_or(tile_amount)
+}
+
+// Pay the salary of every Go tile passed over when moving forward, landing is paid by the tile
+pub(crate) async fn pass_go_tiles(game: &mut Game, start: usize, steps: usize) {
+    for step in 1..steps {
+        let Go { amount } = game.board[(start + step) % game.board.len()] else {
+            continue;
+        };
+        let amount = go_salary(game, amount);
+        game.players[game.player_turn].money += amount;
+        log::debug!(
+            "Player {} passed Go and collected {amount}",
+            game.players[game.player_turn].id
+        );
+        send_to_all_players(
+            &game.players,
+            ServerEvent::PassGo(PlayerGoTileData {
+                player: game.players[game.player_turn].id,
+                amount,
+            }),
+        )
+        .await;
+    }
+}
+
+pub(crate) async fn land_on_tile(game: &mut Game, uuid: &Uuid, roll: u8) {
+    let current_position = game.players[game.player_turn].position;
+    log::debug!("Tile: {:?}", game.board[current_position]);
     match game.board[current_position].clone() {
+        Property {
+            mortgaged: true, ..
+        }
+        | Railroad {
+            mortgaged: true, ..
+        }
+        | Utility {
+            mortgaged: true, ..
+        } => {
+            log::debug!("Tile {current_position} is mortgaged, no rent is due");
+        }
         Property {
             rents,
             level,
             owner,
             costs,
+            group,
             ..
         } => {
-            pay_rent_or_buy(game, uuid, rents[level.clone() as usize], owner, costs[0]).await;
-            return (roll1, roll2);
+            let rent = get_rent_property(&rents, &level, group, owner, game);
+            pay_rent_or_buy(game, uuid, rent, owner, costs[0]).await;
+            return;
         }
         Railroad {
             owner, rents, cost, ..
         } => {
             let rent = get_rent_railroad(&rents, owner, game);
             pay_rent_or_buy(game, uuid, rent, owner, cost).await;
-            return (roll1, roll2);
+            return;
+        }
+        Chance(name) => {
+            if draw_card(game, uuid, CardDeck::from_tile_name(&name), roll).await {
+                return;
+            }
         }
-        Chance { .. } => {}
         Go { amount } => {
+            let mut amount = go_salary(game, amount);
+            if game.rules.double_go_on_landing {
+                amount *= 2;
+            }
             game.players[game.player_turn].money += amount;
             send_to_all_players(
                 &game.players,
-                Action::PlayerGoTile,
-                Some(
-                    to_string(&PlayerGoTileData {
-                        player: game.players[game.player_turn].id,
-                        amount,
-                    })
-                    .unwrap(),
-                ),
+                ServerEvent::PlayerGoTile(PlayerGoTileData {
+                    player: game.players[game.player_turn].id,
+                    amount,
+                }),
             )
             .await;
         }
         Jail => {}
         GoToJail => {
-            game.players[game.player_turn].position = game
-                .board
-                .iter()
-                .position(|tile| matches!(tile, Jail))
-                .unwrap();
-            game.players[game.player_turn].is_in_jail = true;
-            game.players[game.player_turn].jail_turns = 3;
-            send_to_all_players(
-                &game.players,
-                Action::GoToJail,
-                Some(game.players[game.player_turn].id.to_string()),
-            )
-            .await;
+            send_player_to_jail(game).await;
+        }
+        FreeParking => {
+            collect_pot(game, *uuid).await;
         }
-        FreeParking => {}
         Utility { owner, cost, .. } => {
             let rent = calculate_utility_cost(roll, owner, game);
             pay_rent_or_buy(game, uuid, rent, owner, cost).await;
-            return (roll1, roll2);
+            return;
+        }
+        Tax { price } if game.rules.income_tax_choice => {
+            ask_income_tax(game, *uuid, price).await;
+            return;
         }
         Tax { price } | LuxuryTax { price } => {
-            if game.players[game.player_turn].money < price {
-                log::debug!("Player {uuid} does not have enough money to pay tax");
-                game.players[game.player_turn].is_bankrupt = true;
-                send_to_all_players(
-                    &game.players,
-                    Action::PlayerBankrupt,
-                    Some(uuid.to_string()),
-                )
-                .await;
-                game.advance_turn().await;
-                return (roll1, roll2);
+            // The turn resumes once the debt is settled
+            if !pay_tax(game, *uuid, price).await {
+                return;
             }
-            game.players[game.player_turn].money -= price;
-            send_to_all_players(
-                &game.players,
-                Action::PayTax,
-                Some(
-                    to_string(&PlayerPayTaxData {
-                        player: game.players[game.player_turn].id,
-                        amount: price,
-                    })
-                    .unwrap(),
-                ),
-            )
-            .await;
         }
     }
-    game.advance_turn().await;
-    (roll1, roll2)
+    game.end_turn().await;
+}
+
+pub(crate) async fn send_player_to_jail(game: &mut Game) {
+    game.players[game.player_turn].position = game
+        .board
+        .iter()
+        .position(|tile| matches!(tile, Jail))
+        .unwrap();
+    game.players[game.player_turn].is_in_jail = true;
+    game.players[game.player_turn].jail_turns = game.rules.jail_turns;
+    game.players[game.player_turn].doubles_count = 0;
+    send_to_all_players(
+        &game.players,
+        ServerEvent::GoToJail(game.players[game.player_turn].id),
+    )
+    .await;
+}
+
+// Hand the player's cash and tiles to the creditor, or back to the bank when there is none
+pub(crate) async fn declare_bankrupt(game: &mut Game, uuid: &Uuid, creditor: Option<Uuid>) {
+    let mut money = 0;
+    let mut jail_cards = vec![];
+    if let Some(player) = game.players.iter_mut().find(|p| p.id == *uuid) {
+        player.is_bankrupt = true;
+        money = player.money;
+        player.money = 0;
+        jail_cards = std::mem::take(&mut player.jail_cards);
+    }
+    let mut tiles = vec![];
+    for (position, tile) in game.board.iter_mut().enumerate() {
+        if tile.owner() != Some(*uuid) {
+            continue;
+        }
+        // Buildings are sold back to the bank at half their cost
+        if let Property { level, costs, .. } = tile {
+            match level {
+                PropertyLevel::None => {}
+                PropertyLevel::Hotel => game.hotels_available += 1,
+                _ => game.houses_available += level.clone() as u32,
+            }
+            money += level.clone() as u32 * (costs[1] / 2);
+            *level = PropertyLevel::None;
+        }
+        tile.set_owner(creditor);
+        if creditor.is_none() {
+            tile.set_mortgaged(false);
+        }
+        tiles.push(position as u32);
+    }
+    // Get Out of Jail Free cards go to the creditor, or back under their deck
+    let mut handed_cards = 0;
+    match game.players.iter_mut().find(|p| Some(p.id) == creditor) {
+        Some(player) => {
+            player.money += money;
+            handed_cards = jail_cards.len() as u32;
+            player.jail_cards.append(&mut jail_cards);
+        }
+        None => {
+            for deck in jail_cards {
+                return_jail_card(game, deck);
+            }
+        }
+    }
+    game.trades
+        .retain(|trade| trade.from != *uuid && trade.to != *uuid);
+    log::debug!("Player {uuid} is bankrupt, assets go to {creditor:?}");
+    send_to_all_players(
+        &game.players,
+        ServerEvent::PlayerBankrupt(BankruptData {
+            player: *uuid,
+            creditor,
+            money,
+            tiles,
+            jail_cards: handed_cards,
+        }),
+    )
+    .await;
 }
 
 fn calculate_utility_cost(dice_roll: u8, owner: Option<Uuid>, game: &mut Game) -> u32 {
     if let Some(owner) = owner {
         let number_utilities = game.board.iter().filter(|tile| matches!(tile, Utility { owner: Some(tile_owner), .. } if *tile_owner == owner)).count() as u32;
+        let [one, two] = game.rules.utility_multipliers;
         return match number_utilities {
-            1 => 4 * u32::from(dice_roll),
-            2 => 10 * u32::from(dice_roll),
+            1 => one * u32::from(dice_roll),
+            2 => two * u32::from(dice_roll),
             _ => 0,
         };
     }
     0
 }
 
+fn get_rent_property(
+    rents: &[u32],
+    level: &PropertyLevel,
+    group: ColorGroup,
+    owner: Option<Uuid>,
+    game: &Game,
+) -> u32 {
+    let rent = rents[level.clone() as usize];
+    // Unimproved properties collect double rent when the owner holds the whole group
+    match owner {
+        Some(owner_id)
+            if *level == PropertyLevel::None && owns_color_group(&game.board, group, owner_id) =>
+        {
+            rent * 2
+        }
+        _ => rent,
+    }
+}
+
 fn get_rent_railroad(rent: &[u32], owner: Option<Uuid>, game: &Game) -> u32 {
     if let Some(owner_id) = owner {
-        return rent[game
+        // The owner has this railroad, so at least one
+        let count = game
             .board
             .iter()
             .filter(|tile| matches!(tile, Railroad { owner: Some(tile_owner), .. } if *tile_owner == owner_id))
-            .count()];
+            .count();
+        return rent[count - 1];
     }
     0
 }
@@ -181,62 +396,41 @@ async fn pay_rent_or_buy(
     owner: Option<Uuid>,
     cost: u32,
 ) {
-    if owner.is_some() && &owner.unwrap() != uuid {
-        if game.players[game.player_turn].money < rent_price {
-            log::debug!("Player {uuid} does not have enough money to pay rent");
-            game.players[game.player_turn].is_bankrupt = true;
-            send_to_all_players(
-                &game.players,
-                Action::PlayerBankrupt,
-                Some(uuid.to_string()),
-            )
-            .await;
-            game.advance_turn().await;
-            return;
+    if let Some(owner) = owner.filter(|owner| owner != uuid) {
+        match charge(game, *uuid, Some(owner), rent_price).await {
+            Payment::Paid => {
+                log::debug!("Player {uuid} paid rent of {rent_price} to Player {owner}");
+                let pay_rent_data = PayRentData {
+                    rent: rent_price,
+                    owner,
+                    player: *uuid,
+                };
+                send_to_all_players(&game.players, ServerEvent::PayRent(pay_rent_data)).await;
+            }
+            Payment::Debt => return,
+            Payment::Bankrupt => {}
         }
-        game.players[game.player_turn].money -= rent_price;
-        let owner_player: &mut Player = game
-            .players
-            .iter_mut()
-            .find(|player| player.id == owner.unwrap())
-            .unwrap();
-        owner_player.money += rent_price;
-        log::debug!(
-            "Player {} paid rent of {} to Player {}",
-            uuid,
-            rent_price,
-            owner_player.id
-        );
-        let pay_rent_data = PayRentData {
-            rent: rent_price,
-            owner: owner_player.id,
-            player: *uuid,
-        };
-        send_to_all_players(
-            &game.players,
-            PayRent,
-            Some(to_string(&pay_rent_data).unwrap()),
-        )
-        .await;
     } else if game.players[game.player_turn].money >= cost {
         send_to_all_players(
             &game.players,
-            Action::AskBuyProperty,
-            Some(
-                to_string(&BuyPropertyData {
-                    position: game.players[game.player_turn].position as u32,
-                    player: *uuid,
-                })
-                .unwrap(),
-            ),
+            ServerEvent::AskBuyProperty(BuyPropertyData {
+                position: game.players[game.player_turn].position as u32,
+                player: *uuid,
+            }),
         )
         .await;
+        game.phase = TurnPhase::AwaitingBuyDecision;
+        return;
+    } else if owner.is_none() {
+        log::debug!("Player {uuid} can't afford the tile");
+        auction_unsold_tile(game).await;
         return;
     }
-    game.advance_turn().await;
+    game.end_turn().await;
 }
 
-pub(crate) async fn buy_property(uuid: Uuid, game: &mut Game) {
+// The player stays on the buy decision and can still decline, which auctions the tile
+pub(crate) async fn buy_property(uuid: Uuid, game: &mut Game) -> Result<(), BuyError> {
     let tile = &mut game.board[game.players[game.player_turn].position];
     let player = game.players.iter_mut().find(|p| p.id == uuid).unwrap();
 
@@ -256,18 +450,15 @@ pub(crate) async fn buy_property(uuid: Uuid, game: &mut Game) {
                 );
                 send_to_all_players(
                     &game.players,
-                    Action::BuyProperty,
-                    Some(
-                        to_string(&BuyPropertyData {
-                            player: uuid,
-                            position: game.players[game.player_turn].position as u32,
-                        })
-                        .unwrap(),
-                    ),
+                    ServerEvent::BuyProperty(BuyPropertyData {
+                        player: uuid,
+                        position: game.players[game.player_turn].position as u32,
+                    }),
                 )
                 .await;
             } else {
                 log::debug!("Player {} does not have enough money to buy property", uuid);
+                return Err(BuyError::NotEnoughMoney);
             }
         }
         Railroad {
@@ -278,6 +469,7 @@ pub(crate) async fn buy_property(uuid: Uuid, game: &mut Game) {
         | Utility {
             ref mut owner,
             cost,
+            ..
         } if owner.is_none() => {
             if player.money >= *cost {
                 player.money -= *cost;
@@ -289,21 +481,19 @@ pub(crate) async fn buy_property(uuid: Uuid, game: &mut Game) {
                 );
                 send_to_all_players(
                     &game.players,
-                    Action::BuyProperty,
-                    Some(
-                        to_string(&BuyPropertyData {
-                            player: uuid,
-                            position: game.players[game.player_turn].position as u32,
-                        })
-                        .unwrap(),
-                    ),
+                    ServerEvent::BuyProperty(BuyPropertyData {
+                        player: uuid,
+                        position: game.players[game.player_turn].position as u32,
+                    }),
                 )
                 .await;
             } else {
                 log::debug!("Player {uuid} does not have enough money to buy property");
+                return Err(BuyError::NotEnoughMoney);
             }
         }
         _ => {}
     }
-    game.advance_turn().await;
+    game.end_turn().await;
+    Ok(())
 }
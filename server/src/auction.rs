@@ -0,0 +1,282 @@
+use crate::building::{can_build_house, place_building, settle_house_requests, HouseRequest};
+use crate::communication::send_to_all_players;
+use crate::error::GameError;
+use crate::game_state::{Game, TurnPhase};
+use crate::server_state::ServerState;
+use shared::action::{
+    AuctionEndData, AuctionItem, AuctionStartData, BidData, ErrorCode, ServerEvent,
+};
+use shared::list_const::{AUCTION_BID_SECONDS, AUCTION_MIN_INCREMENT};
+use std::fmt;
+use std::sync::Arc;
+use tokio::time::{sleep_until, Duration, Instant};
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub(crate) struct Auction {
+    pub(crate) id: Uuid,
+    pub(crate) item: AuctionItem,
+    pub(crate) bidders: Vec<Uuid>,
+    pub(crate) highest_bid: u32,
+    pub(crate) highest_bidder: Option<Uuid>,
+    pub(crate) deadline: Instant,
+    // Tiles the bidders asked a house for, in a house auction
+    pub(crate) requests: Vec<HouseRequest>,
+    // Phase the turn goes back to once a house is auctioned
+    pub(crate) resume_phase: TurnPhase,
+}
+
+impl Auction {
+    pub(crate) fn minimum_bid(&self) -> u32 {
+        match self.highest_bidder {
+            Some(_) => self.highest_bid + AUCTION_MIN_INCREMENT,
+            None => self.highest_bid,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum AuctionError {
+    NoAuction,
+    NotABidder,
+    BidTooLow(u32),
+    NotEnoughMoney,
+}
+
+impl fmt::Display for AuctionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AuctionError::NoAuction => write!(f, "There is no auction in progress"),
+            AuctionError::NotABidder => write!(f, "You can't bid in this auction"),
+            AuctionError::BidTooLow(minimum) => write!(f, "The bid must be at least {minimum}"),
+            AuctionError::NotEnoughMoney => write!(f, "Not enough money for this bid"),
+        }
+    }
+}
+
+impl GameError for AuctionError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            AuctionError::NotEnoughMoney => ErrorCode::InsufficientFunds,
+            _ => ErrorCode::IllegalMove,
+        }
+    }
+}
+
+pub(crate) async fn start_auction(
+    game: &mut Game,
+    item: AuctionItem,
+    bidders: Vec<Uuid>,
+    starting_price: u32,
+    requests: Vec<HouseRequest>,
+) {
+    log::debug!("Starting auction for {item:?} at {starting_price}");
+    game.auction = Some(Auction {
+        id: Uuid::new_v4(),
+        item: item.clone(),
+        bidders: bidders.clone(),
+        highest_bid: starting_price,
+        highest_bidder: None,
+        deadline: Instant::now() + Duration::from_secs(AUCTION_BID_SECONDS),
+        requests,
+        resume_phase: game.phase,
+    });
+    game.phase = TurnPhase::Auction;
+    send_to_all_players(
+        &game.players,
+        ServerEvent::AuctionStart(AuctionStartData {
+            item,
+            bidders,
+            starting_price,
+        }),
+    )
+    .await;
+}
+
+// A tile the player declined or can't afford is auctioned to every player still in the game
+// when the rules ask for it, otherwise it stays with the bank
+pub(crate) async fn auction_unsold_tile(game: &mut Game) {
+    if !game.rules.mandatory_auctions {
+        game.end_turn().await;
+        return;
+    }
+    let position = game.players[game.player_turn].position as u32;
+    let bidders = game
+        .players
+        .iter()
+        .filter(|player| !player.is_bankrupt)
+        .map(|player| player.id)
+        .collect();
+    start_auction(
+        game,
+        AuctionItem::Property(position),
+        bidders,
+        AUCTION_MIN_INCREMENT,
+        vec![],
+    )
+    .await;
+}
+
+pub(crate) async fn place_bid(
+    game: &mut Game,
+    uuid: Uuid,
+    amount: u32,
+) -> Result<(), AuctionError> {
+    let Some(auction) = game.auction.as_mut() else {
+        return Err(AuctionError::NoAuction);
+    };
+    if !auction.bidders.contains(&uuid) {
+        return Err(AuctionError::NotABidder);
+    }
+    if amount < auction.minimum_bid() {
+        return Err(AuctionError::BidTooLow(auction.minimum_bid()));
+    }
+    let player = game.players.iter().find(|p| p.id == uuid).unwrap();
+    if player.money < amount {
+        return Err(AuctionError::NotEnoughMoney);
+    }
+    // Every new bid restarts the countdown
+    auction.highest_bid = amount;
+    auction.highest_bidder = Some(uuid);
+    auction.deadline = Instant::now() + Duration::from_secs(AUCTION_BID_SECONDS);
+    log::debug!("Player {uuid} bid {amount}");
+    send_to_all_players(
+        &game.players,
+        ServerEvent::PlaceBid(BidData {
+            player: uuid,
+            amount,
+        }),
+    )
+    .await;
+    Ok(())
+}
+
+pub(crate) async fn end_auction(game: &mut Game) {
+    let Some(auction) = game.auction.take() else {
+        return;
+    };
+    log::debug!(
+        "Auction for {:?} ended, winner {:?} with {}",
+        auction.item,
+        auction.highest_bidder,
+        auction.highest_bid
+    );
+    send_to_all_players(
+        &game.players,
+        ServerEvent::AuctionEnd(AuctionEndData {
+            item: auction.item.clone(),
+            winner: auction.highest_bidder,
+            amount: auction.highest_bid,
+        }),
+    )
+    .await;
+    match auction.item {
+        AuctionItem::House => {
+            game.phase = auction.resume_phase;
+            // Nobody wanted a house at the starting price, the requests are dropped
+            let Some(winner) = auction.highest_bidder else {
+                return;
+            };
+            // The house goes on the tile the winner asked for, as long as it can still take one
+            let position = auction
+                .requests
+                .iter()
+                .find(|request| request.player == winner)
+                .map(|request| request.position)
+                .filter(|position| can_build_house(game, winner, *position));
+            let can_pay = game
+                .players
+                .iter()
+                .any(|p| p.id == winner && p.money >= auction.highest_bid);
+            if let Some(position) = position {
+                if can_pay && game.houses_available > 0 {
+                    place_building(game, winner, position, auction.highest_bid).await;
+                }
+            }
+            // The other requests go for the houses left
+            game.house_requests = auction
+                .requests
+                .into_iter()
+                .filter(|request| request.player != winner)
+                .collect();
+            settle_house_requests(game).await;
+        }
+        AuctionItem::Property(position) => {
+            // A winner who left the game doesn't buy the tile
+            let winner = auction
+                .highest_bidder
+                .and_then(|winner| game.players.iter_mut().find(|p| p.id == winner));
+            if let Some(player) = winner {
+                if player.money >= auction.highest_bid {
+                    player.money -= auction.highest_bid;
+                    game.board[position as usize].set_owner(Some(player.id));
+                    log::debug!("Player {} won tile {position}", player.id);
+                }
+            }
+            // The auction closes the turn of the player who declined to buy
+            game.end_turn().await;
+            // House requests made during the auction are settled now
+            settle_house_requests(game).await;
+        }
+    }
+}
+
+// The player leaves the auction along with their bid
+pub(crate) fn remove_bidder(game: &mut Game, uuid: Uuid) {
+    let Some(auction) = game.auction.as_mut() else {
+        return;
+    };
+    auction.bidders.retain(|bidder| *bidder != uuid);
+    if auction.highest_bidder == Some(uuid) {
+        auction.highest_bidder = None;
+    }
+}
+
+// Closes the auction without a sale or a turn change
+pub(crate) async fn cancel_auction(game: &mut Game) {
+    let Some(auction) = game.auction.take() else {
+        return;
+    };
+    log::debug!("Auction for {:?} cancelled", auction.item);
+    send_to_all_players(
+        &game.players,
+        ServerEvent::AuctionEnd(AuctionEndData {
+            item: auction.item,
+            winner: None,
+            amount: auction.highest_bid,
+        }),
+    )
+    .await;
+}
+
+// Close the auction once nobody has bid for the countdown duration
+pub(crate) async fn run_auction_timer(state: Arc<ServerState>, game_id: Uuid, auction_id: Uuid) {
+    let mut auction_id = auction_id;
+    loop {
+        let deadline = {
+            let games = state.active_games.lock().await;
+            match games.get(&game_id).and_then(|game| game.auction.as_ref()) {
+                Some(auction) if auction.id == auction_id => auction.deadline,
+                _ => return,
+            }
+        };
+        sleep_until(deadline).await;
+        let mut games = state.active_games.lock().await;
+        let Some(game) = games.get_mut(&game_id) else {
+            return;
+        };
+        match &game.auction {
+            Some(auction) if auction.id == auction_id => {
+                if Instant::now() >= auction.deadline {
+                    end_auction(game).await;
+                    // A house auction can open the next one for the houses left
+                    match &game.auction {
+                        Some(next) => auction_id = next.id,
+                        None => return,
+                    }
+                }
+            }
+            _ => return,
+        }
+    }
+}
@@ -0,0 +1,313 @@
+use crate::auction::{run_auction_timer, start_auction};
+use crate::communication::send_to_all_players;
+use crate::error::GameError;
+use crate::game_state::Game;
+use crate::server_state::ServerState;
+use shared::action::{AuctionItem, BuildingData, ErrorCode, HouseRequestData, ServerEvent};
+use shared::board::Tile::Property;
+use shared::board::{owns_color_group, ColorGroup, PropertyLevel};
+use shared::list_const::AUCTION_BID_SECONDS;
+use std::fmt;
+use std::sync::Arc;
+use tokio::time::{sleep, Duration};
+use uuid::Uuid;
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum BuildError {
+    NotAProperty,
+    NoMonopoly,
+    MaxLevel,
+    NothingToSell,
+    UnevenBuild,
+    NotEnoughMoney,
+    NoHousesLeft,
+    NoHotelsLeft,
+    Mortgaged,
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            BuildError::NotAProperty => "This tile is not a property",
+            BuildError::NoMonopoly => "You must own the whole color group to build",
+            BuildError::MaxLevel => "This property already has a hotel",
+            BuildError::NothingToSell => "There is no building to sell on this property",
+            BuildError::UnevenBuild => "Buildings must be spread evenly across the color group",
+            BuildError::NotEnoughMoney => "Not enough money to build",
+            BuildError::NoHousesLeft => "The bank has no houses left",
+            BuildError::NoHotelsLeft => "The bank has no hotels left",
+            BuildError::Mortgaged => "A property of the color group is mortgaged",
+        };
+        write!(f, "{message}")
+    }
+}
+
+impl GameError for BuildError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            BuildError::NotEnoughMoney => ErrorCode::InsufficientFunds,
+            _ => ErrorCode::IllegalMove,
+        }
+    }
+}
+
+// Levels of every property in a color group
+fn group_levels(game: &Game, group: ColorGroup) -> Vec<usize> {
+    game.board
+        .iter()
+        .filter_map(|tile| match tile {
+            Property {
+                group: tile_group,
+                level,
+                ..
+            } if *tile_group == group => Some(level.clone() as usize),
+            _ => None,
+        })
+        .collect()
+}
+
+// Check that the player can add a building on the tile, returns its level and the house cost
+fn check_build(
+    game: &Game,
+    uuid: Uuid,
+    position: usize,
+) -> Result<(PropertyLevel, u32), BuildError> {
+    let Some(Property {
+        owner,
+        level,
+        costs,
+        group,
+        ..
+    }) = game.board.get(position)
+    else {
+        return Err(BuildError::NotAProperty);
+    };
+    if *owner != Some(uuid) || !owns_color_group(&game.board, *group, uuid) {
+        return Err(BuildError::NoMonopoly);
+    }
+    if *level == PropertyLevel::Hotel {
+        return Err(BuildError::MaxLevel);
+    }
+    if game.board.iter().any(|tile| {
+        matches!(tile, Property { group: tile_group, .. } if tile_group == group)
+            && tile.is_mortgaged()
+    }) {
+        return Err(BuildError::Mortgaged);
+    }
+    // Even-build rule: a property can't get ahead of the rest of its group
+    let current_level = level.clone() as usize;
+    if game.rules.even_build
+        && group_levels(game, *group)
+            .iter()
+            .any(|other| *other < current_level)
+    {
+        return Err(BuildError::UnevenBuild);
+    }
+    let player = game.players.iter().find(|p| p.id == uuid).unwrap();
+    if player.money < costs[1] {
+        return Err(BuildError::NotEnoughMoney);
+    }
+    Ok((level.clone(), costs[1]))
+}
+
+// Whether the player could add a house (not a hotel) on the tile
+pub(crate) fn can_build_house(game: &Game, uuid: Uuid, position: usize) -> bool {
+    matches!(check_build(game, uuid, position), Ok((level, _)) if level != PropertyLevel::House4)
+}
+
+// A house asked for while the bank is short of houses
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct HouseRequest {
+    pub(crate) player: Uuid,
+    pub(crate) position: usize,
+}
+
+pub(crate) async fn build_house(
+    uuid: Uuid,
+    game: &mut Game,
+    position: usize,
+) -> Result<(), BuildError> {
+    let (level, house_cost) = check_build(game, uuid, position)?;
+    if level == PropertyLevel::House4 {
+        if game.hotels_available == 0 {
+            return Err(BuildError::NoHotelsLeft);
+        }
+    } else {
+        if game.houses_available == 0 {
+            return Err(BuildError::NoHousesLeft);
+        }
+        // Building shortage: the request waits for the other players who want a house
+        let builders = game
+            .players
+            .iter()
+            .filter(|p| {
+                !p.is_bankrupt
+                    && (0..game.board.len()).any(|tile| can_build_house(game, p.id, tile))
+            })
+            .count();
+        if !game.house_requests.is_empty() || (game.houses_available as usize) < builders {
+            request_house(game, uuid, position).await;
+            return Ok(());
+        }
+    }
+    place_building(game, uuid, position, house_cost).await;
+    Ok(())
+}
+
+// A new request replaces the player's previous one
+async fn request_house(game: &mut Game, uuid: Uuid, position: usize) {
+    game.house_requests.retain(|request| request.player != uuid);
+    game.house_requests.push(HouseRequest {
+        player: uuid,
+        position,
+    });
+    log::debug!("Player {uuid} asked for a house on tile {position} during a shortage");
+    send_to_all_players(
+        &game.players,
+        ServerEvent::RequestHouse(HouseRequestData {
+            player: uuid,
+            position: position as u32,
+        }),
+    )
+    .await;
+}
+
+// Build the requested houses when the bank has enough, otherwise auction them one at a time.
+// The requests wait while another auction runs, its end settles them
+pub(crate) async fn settle_house_requests(game: &mut Game) {
+    if game.auction.is_some() {
+        return;
+    }
+    let requests: Vec<HouseRequest> = std::mem::take(&mut game.house_requests)
+        .into_iter()
+        .filter(|request| can_build_house(game, request.player, request.position))
+        .collect();
+    if requests.len() <= game.houses_available as usize {
+        for request in requests {
+            let house_cost = check_build(game, request.player, request.position)
+                .map_or(0, |(_, house_cost)| house_cost);
+            place_building(game, request.player, request.position, house_cost).await;
+        }
+        return;
+    }
+    if game.houses_available == 0 {
+        return;
+    }
+    log::debug!("Building shortage, starting a house auction");
+    let bidders = requests.iter().map(|request| request.player).collect();
+    let starting_price = requests
+        .iter()
+        .filter_map(|request| check_build(game, request.player, request.position).ok())
+        .map(|(_, house_cost)| house_cost)
+        .min()
+        .unwrap_or(0);
+    start_auction(game, AuctionItem::House, bidders, starting_price, requests).await;
+}
+
+// Settle the requests once the other players had the time to ask for a house
+pub(crate) async fn run_house_request_timer(state: Arc<ServerState>, game_id: Uuid) {
+    sleep(Duration::from_secs(AUCTION_BID_SECONDS)).await;
+    let auction_id = {
+        let mut games = state.active_games.lock().await;
+        let Some(game) = games.get_mut(&game_id) else {
+            return;
+        };
+        settle_house_requests(game).await;
+        match &game.auction {
+            Some(auction) if auction.item == AuctionItem::House => auction.id,
+            _ => return,
+        }
+    };
+    run_auction_timer(state, game_id, auction_id).await;
+}
+
+// Add a building on the tile and charge the player the given price
+pub(crate) async fn place_building(game: &mut Game, uuid: Uuid, position: usize, price: u32) {
+    let Property { level, .. } = &game.board[position] else {
+        return;
+    };
+    let new_level = PropertyLevel::from_index(level.clone() as usize + 1).unwrap();
+    if new_level == PropertyLevel::Hotel {
+        // The four houses go back to the bank when upgrading to a hotel
+        game.hotels_available -= 1;
+        game.houses_available += 4;
+    } else {
+        game.houses_available -= 1;
+    }
+    let player = game.players.iter_mut().find(|p| p.id == uuid).unwrap();
+    player.money -= price;
+    set_level(game, position, new_level.clone());
+    log::debug!("Player {uuid} built on tile {position}, now {new_level:?}");
+    send_to_all_players(
+        &game.players,
+        ServerEvent::BuildHouse(BuildingData {
+            player: uuid,
+            position: position as u32,
+            level: new_level,
+            price,
+        }),
+    )
+    .await;
+}
+
+pub(crate) async fn sell_house(
+    uuid: Uuid,
+    game: &mut Game,
+    position: usize,
+) -> Result<(), BuildError> {
+    let Some(Property {
+        owner,
+        level,
+        costs,
+        group,
+        ..
+    }) = game.board.get(position).cloned()
+    else {
+        return Err(BuildError::NotAProperty);
+    };
+    if owner != Some(uuid) || level == PropertyLevel::None {
+        return Err(BuildError::NothingToSell);
+    }
+    // Even-build rule applies in reverse when selling
+    let current_level = level.clone() as usize;
+    if game.rules.even_build
+        && group_levels(game, group)
+            .iter()
+            .any(|other| *other > current_level)
+    {
+        return Err(BuildError::UnevenBuild);
+    }
+    if level == PropertyLevel::Hotel {
+        // Breaking a hotel needs four houses from the bank
+        if game.houses_available < 4 {
+            return Err(BuildError::NoHousesLeft);
+        }
+        game.houses_available -= 4;
+        game.hotels_available += 1;
+    } else {
+        game.houses_available += 1;
+    }
+    let refund = costs[1] / 2;
+    let player = game.players.iter_mut().find(|p| p.id == uuid).unwrap();
+    player.money += refund;
+    let new_level = PropertyLevel::from_index(current_level - 1).unwrap();
+    set_level(game, position, new_level.clone());
+    log::debug!("Player {uuid} sold a building on tile {position}, now {new_level:?}");
+    send_to_all_players(
+        &game.players,
+        ServerEvent::SellHouse(BuildingData {
+            player: uuid,
+            position: position as u32,
+            level: new_level,
+            price: refund,
+        }),
+    )
+    .await;
+    Ok(())
+}
+
+fn set_level(game: &mut Game, position: usize, new_level: PropertyLevel) {
+    if let Property { level, .. } = &mut game.board[position] {
+        *level = new_level;
+    }
+}
@@ -0,0 +1,136 @@
+use crate::action::{land_on_tile, pass_go_tiles, send_player_to_jail};
+use crate::communication::send_to_all_players;
+use crate::debt::{charge, charge_many, Payment};
+use crate::game_state::Game;
+use rand::seq::SliceRandom;
+use shared::action::{DrawCardData, ServerEvent};
+use shared::board::count_buildings;
+use shared::cards::{Card, CardDeck, CardEffect};
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub(crate) struct Deck {
+    pub(crate) cards: Vec<Card>,
+}
+
+impl Deck {
+    pub(crate) fn new(cards: &[Card]) -> Self {
+        let mut cards = cards.to_vec();
+        cards.shuffle(&mut rand::rng());
+        Deck { cards }
+    }
+
+    // Take the top card and put it back at the bottom of the deck
+    pub(crate) fn draw(&mut self) -> Card {
+        self.cards.rotate_left(1);
+        self.cards.last().unwrap().clone()
+    }
+}
+
+pub(crate) fn deck_mut(game: &mut Game, deck: CardDeck) -> &mut Deck {
+    match deck {
+        CardDeck::Chance => &mut game.chance_cards,
+        CardDeck::CommunityChest => &mut game.community_chest_cards,
+    }
+}
+
+// A Get Out of Jail Free card that leaves a player goes back under its deck
+pub(crate) fn return_jail_card(game: &mut Game, deck: CardDeck) {
+    if let Some(card) = deck
+        .cards()
+        .iter()
+        .find(|card| card.effect == CardEffect::GetOutOfJailFree)
+    {
+        deck_mut(game, deck).cards.push(card.clone());
+    }
+}
+
+// Returns true when the card moved the player or left a debt, the turn is then handled elsewhere
+pub(crate) async fn draw_card(game: &mut Game, uuid: &Uuid, deck: CardDeck, roll: u8) -> bool {
+    let card = deck_mut(game, deck).draw();
+    log::debug!("Player {uuid} drew card: {}", card.description);
+    send_to_all_players(
+        &game.players,
+        ServerEvent::DrawCard(DrawCardData {
+            player: *uuid,
+            deck,
+            card: card.clone(),
+        }),
+    )
+    .await;
+    match card.effect {
+        CardEffect::Collect(amount) => {
+            game.players[game.player_turn].money += amount;
+        }
+        CardEffect::Pay(amount) => {
+            return charge(game, *uuid, None, amount).await == Payment::Debt;
+        }
+        CardEffect::MoveTo(target) => {
+            // The map loader makes sure the target is on the board
+            let Some(position) = target.position(&game.board) else {
+                log::debug!("Card target {target:?} is not on the board");
+                return false;
+            };
+            // Advancing goes forward, passing Go on the way
+            let board_len = game.board.len();
+            let start = game.players[game.player_turn].position;
+            pass_go_tiles(game, start, (position + board_len - start) % board_len).await;
+            move_player_to(game, uuid, position, roll).await;
+            return true;
+        }
+        CardEffect::MoveBack(spaces) => {
+            let board_len = game.board.len();
+            let position = (game.players[game.player_turn].position + board_len
+                - spaces % board_len)
+                % board_len;
+            move_player_to(game, uuid, position, roll).await;
+            return true;
+        }
+        CardEffect::GoToJail => {
+            send_player_to_jail(game).await;
+        }
+        CardEffect::GetOutOfJailFree => {
+            // The player keeps the card, it leaves the deck until used
+            deck_mut(game, deck).cards.pop();
+            game.players[game.player_turn].jail_cards.push(deck);
+        }
+        CardEffect::PayEachPlayer(amount) => {
+            let payments: Vec<(Option<Uuid>, u32)> = game
+                .players
+                .iter()
+                .filter(|p| p.id != *uuid && !p.is_bankrupt)
+                .map(|p| (Some(p.id), amount))
+                .collect();
+            return charge_many(game, *uuid, &payments).await == Payment::Debt;
+        }
+        CardEffect::CollectFromEachPlayer(amount) => {
+            let others: Vec<Uuid> = game
+                .players
+                .iter()
+                .filter(|p| p.id != *uuid && !p.is_bankrupt)
+                .map(|p| p.id)
+                .collect();
+            let mut debt = false;
+            for other in others {
+                debt |= charge(game, other, Some(*uuid), amount).await == Payment::Debt;
+            }
+            return debt;
+        }
+        CardEffect::Repairs {
+            per_house,
+            per_hotel,
+        } => {
+            let (houses, hotels) = count_buildings(&game.board, *uuid);
+            let amount = houses * per_house + hotels * per_hotel;
+            return charge(game, *uuid, None, amount).await == Payment::Debt;
+        }
+    }
+    false
+}
+
+async fn move_player_to(game: &mut Game, uuid: &Uuid, position: usize, roll: u8) {
+    game.players[game.player_turn].position = position;
+    log::debug!("Player {uuid} moved to position {position}");
+    send_to_all_players(&game.players, ServerEvent::Move(position)).await;
+    Box::pin(land_on_tile(game, uuid, roll)).await;
+}
@@ -1,74 +1,335 @@
 use crate::action::{buy_property, roll_dice};
-use crate::game_state::{start_new_game, Player};
+use crate::auction::{
+    auction_unsold_tile, cancel_auction, place_bid, remove_bidder, run_auction_timer,
+};
+use crate::building::{build_house, run_house_request_timer, sell_house};
+use crate::debt::{declare_bankruptcy, is_debtor, remove_from_debts, settle_debts};
+#[cfg(feature = "debug-commands")]
+use crate::debug::run_debug_command;
+use crate::dice::{take_bus, teleport};
+use crate::error::GameError;
+use crate::game_state::{start_new_game, Game, Player, TurnPhase};
+use crate::handshake::check_handshake;
+use crate::jail::{pay_jail_fine, use_jail_card};
+use crate::mortgage::{mortgage, unmortgage};
 use crate::server_state::ServerState;
-use shared::action::{Action, PlayerAction};
-use shared::board::Tile::Property;
-use shared::list_const::NUMBER_PLAYERS_PER_GAME;
+use crate::tax::choose_income_tax;
+use crate::trade::{accept_trade, counter_trade, propose_trade, reject_trade, TradeError};
+use shared::action::{ClientCommand, ErrorCode, HandshakeRejectedData, ServerEvent, WelcomeData};
+use shared::list_const::{CAPABILITIES, NUMBER_PLAYERS_PER_GAME, PROTOCOL_VERSION};
+use std::fmt;
+use std::io::ErrorKind;
 use std::sync::Arc;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::tcp::OwnedWriteHalf;
 use tokio::net::TcpStream;
 use tokio::sync::mpsc;
 use uuid::Uuid;
 
-pub(crate) async fn send_to_all_players(
-    players: &Vec<Player>,
-    action: Action,
-    data: Option<String>,
-) {
+#[derive(Debug, PartialEq)]
+pub(crate) enum CommandError {
+    Malformed(String),
+    NotStarted,
+    GameOver,
+    NotYourTurn,
+    WrongPhase(TurnPhase),
+    #[cfg(not(feature = "debug-commands"))]
+    DebugDisabled,
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CommandError::Malformed(e) => write!(f, "Malformed message: {e}"),
+            CommandError::NotStarted => write!(f, "The game hasn't started yet"),
+            CommandError::GameOver => write!(f, "The game is over"),
+            CommandError::NotYourTurn => write!(f, "It's not your turn"),
+            CommandError::WrongPhase(phase) => {
+                let message = match phase {
+                    TurnPhase::AwaitingRoll => "Roll the dice first",
+                    TurnPhase::InJailDecision => "Roll, pay the fine or use a card to leave jail",
+                    TurnPhase::AwaitingMoveChoice => "Choose where to move first",
+                    TurnPhase::AwaitingTaxChoice => "Choose how to pay the income tax first",
+                    TurnPhase::AwaitingBuyDecision => "Choose whether to buy the tile first",
+                    TurnPhase::Auction => "An auction is in progress",
+                    TurnPhase::PayingDebt => "Waiting for a debt to be paid",
+                    TurnPhase::TurnEnd => "The turn is over",
+                };
+                write!(f, "{message}")
+            }
+            #[cfg(not(feature = "debug-commands"))]
+            CommandError::DebugDisabled => {
+                write!(f, "Debug commands are disabled on this server")
+            }
+        }
+    }
+}
+
+impl GameError for CommandError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            CommandError::Malformed(_) => ErrorCode::MalformedMessage,
+            CommandError::NotStarted | CommandError::NotYourTurn => ErrorCode::NotYourTurn,
+            _ => ErrorCode::IllegalMove,
+        }
+    }
+}
+
+pub(crate) async fn send_to_all_players(players: &Vec<Player>, event: ServerEvent) {
     for player in players {
-        send_message(player, action.clone(), data.clone()).await;
+        send_message(player, event.clone()).await;
     }
 }
-pub(crate) async fn send_message(player: &Player, action: Action, data: Option<String>) {
-    let action = PlayerAction {
-        action_type: action,
-        data, // Add specific data if required
-    };
-    let mut serialized_action = serde_json::to_string(&action).unwrap();
-    serialized_action.push('\n');
-    let _ = player.tx.send(serialized_action).await;
+pub(crate) async fn send_message(player: &Player, event: ServerEvent) {
+    let mut serialized_event = serde_json::to_string(&event).unwrap();
+    serialized_event.push('\n');
+    let _ = player.tx.send(serialized_event).await;
+}
+
+// Writes straight to the socket, for players not yet reachable through their channel
+async fn write_event(writer: &mut OwnedWriteHalf, event: &ServerEvent) -> std::io::Result<()> {
+    let mut serialized_event = serde_json::to_string(event).unwrap();
+    serialized_event.push('\n');
+    writer.write_all(serialized_event.as_bytes()).await
+}
+
+pub(crate) async fn send_error(game: &Game, uuid: Uuid, error: &impl GameError) {
+    if let Some(player) = game.players.iter().find(|p| p.id == uuid) {
+        send_message(player, error.to_event()).await;
+    }
 }
 
 pub(crate) async fn handle_message_in_game(message: &str, state: &Arc<ServerState>, uuid: Uuid) {
-    let action: PlayerAction = serde_json::from_str(message).unwrap();
+    let parsed = parse_command(message);
     let mut active_games = state.active_games.lock().await;
-    for (_, game) in active_games.iter_mut() {
-        if game.players[game.player_turn].id == uuid {
-            match action.action_type {
-                Action::Roll => {
-                    roll_dice(game, &uuid).await;
+    for (game_id, game) in active_games.iter_mut() {
+        if !game.players.iter().any(|player| player.id == uuid) {
+            continue;
+        }
+        let command = match parsed {
+            Ok(command) => command,
+            Err(e) => {
+                send_error(game, uuid, &e).await;
+                break;
+            }
+        };
+        if let Err(e) = check_phase(game, uuid, &command) {
+            send_error(game, uuid, &e).await;
+            break;
+        }
+        let previous_auction = game.auction.as_ref().map(|auction| auction.id);
+        let had_house_requests = !game.house_requests.is_empty();
+        if let ClientCommand::PlaceBid(amount) = command {
+            if let Err(e) = place_bid(game, uuid, amount).await {
+                send_error(game, uuid, &e).await;
+            }
+            break;
+        }
+        if let Some(result) = handle_trade(game, uuid, &command).await {
+            if let Err(e) = result {
+                send_error(game, uuid, &e).await;
+            }
+            settle_debts(game).await;
+            break;
+        }
+        match command {
+            ClientCommand::Roll => {
+                roll_dice(game, &uuid).await;
+            }
+            ClientCommand::TakeBus(steps) => {
+                if let Err(e) = take_bus(game, uuid, steps).await {
+                    send_error(game, uuid, &e).await;
                 }
-                Action::BuyProperty => {
-                    buy_property(uuid, game).await;
+            }
+            ClientCommand::Teleport(target) => {
+                if let Err(e) = teleport(game, uuid, target).await {
+                    send_error(game, uuid, &e).await;
                 }
-                Action::SkipBuyProperty => {
-                    log::debug!("Player {uuid} skipped buying property");
-                    send_to_all_players(
-                        &game.players,
-                        Action::SkipBuyProperty,
-                        Some(game.players[game.player_turn].id.to_string()),
-                    )
-                    .await;
-                    game.advance_turn().await;
+            }
+            ClientCommand::ChooseIncomeTax(choice) => {
+                if let Err(e) = choose_income_tax(game, uuid, choice).await {
+                    send_error(game, uuid, &e).await;
                 }
-                Action::BuyAll => {
-                    // Buy all properties for debug purpose only
-                    log::debug!("Player {uuid} bought all properties");
-                    for tile in &mut game.board {
-                        if let Property { owner, .. } = tile {
-                            *owner = Some(uuid);
-                        }
-                    }
+            }
+            ClientCommand::BuyProperty => {
+                if let Err(e) = buy_property(uuid, game).await {
+                    send_error(game, uuid, &e).await;
                 }
-                _ => {}
             }
-            break;
+            ClientCommand::SkipBuyProperty => {
+                log::debug!("Player {uuid} skipped buying property");
+                send_to_all_players(
+                    &game.players,
+                    ServerEvent::SkipBuyProperty(game.players[game.player_turn].id),
+                )
+                .await;
+                auction_unsold_tile(game).await;
+            }
+            ClientCommand::BuildHouse(position) => {
+                if let Err(e) = build_house(uuid, game, position).await {
+                    send_error(game, uuid, &e).await;
+                }
+            }
+            ClientCommand::SellHouse(position) => {
+                if let Err(e) = sell_house(uuid, game, position).await {
+                    send_error(game, uuid, &e).await;
+                }
+            }
+            ClientCommand::Mortgage(position) => {
+                if let Err(e) = mortgage(uuid, game, position).await {
+                    send_error(game, uuid, &e).await;
+                }
+            }
+            ClientCommand::Unmortgage(position) => {
+                if let Err(e) = unmortgage(uuid, game, position).await {
+                    send_error(game, uuid, &e).await;
+                }
+            }
+            ClientCommand::PayJailFine => {
+                if let Err(e) = pay_jail_fine(game, uuid).await {
+                    send_error(game, uuid, &e).await;
+                }
+            }
+            ClientCommand::UseJailCard => {
+                if let Err(e) = use_jail_card(game, uuid).await {
+                    send_error(game, uuid, &e).await;
+                }
+            }
+            ClientCommand::DeclareBankruptcy => {
+                if let Err(e) = declare_bankruptcy(game, uuid).await {
+                    send_error(game, uuid, &e).await;
+                }
+            }
+            #[cfg(feature = "debug-commands")]
+            ClientCommand::Debug(command) => {
+                if let Err(e) = run_debug_command(game, uuid, command).await {
+                    send_error(game, uuid, &e).await;
+                }
+            }
+            _ => {}
+        }
+        settle_debts(game).await;
+        // Start the countdown of an auction opened by this action
+        if let Some(auction) = &game.auction {
+            if previous_auction != Some(auction.id) {
+                tokio::spawn(run_auction_timer(Arc::clone(state), *game_id, auction.id));
+            }
         }
+        // Open the window for the other players to ask for a house
+        if !had_house_requests && !game.house_requests.is_empty() {
+            tokio::spawn(run_house_request_timer(Arc::clone(state), *game_id));
+        }
+        break;
     }
 }
 
-pub(crate) async fn handle_message(message: &str, _state: &Arc<ServerState>, _uuid: Uuid) {
-    let _action: PlayerAction = serde_json::from_str(message).unwrap();
+// Commands the current phase allows, trades can be negotiated outside of the player's turn
+fn check_phase(game: &Game, uuid: Uuid, command: &ClientCommand) -> Result<(), CommandError> {
+    // Nothing changes a finished game, trades included
+    if !game.is_active {
+        return Err(CommandError::GameOver);
+    }
+    // Developer commands run at any time
+    #[cfg(feature = "debug-commands")]
+    if matches!(command, ClientCommand::Debug(_)) {
+        return Ok(());
+    }
+    let is_trade = matches!(
+        command,
+        ClientCommand::ProposeTrade(_)
+            | ClientCommand::CounterTrade(_)
+            | ClientCommand::AcceptTrade(_)
+            | ClientCommand::RejectTrade(_)
+    );
+    let manages_tiles = matches!(
+        command,
+        ClientCommand::BuildHouse(_)
+            | ClientCommand::SellHouse(_)
+            | ClientCommand::Mortgage(_)
+            | ClientCommand::Unmortgage(_)
+    );
+    let allowed = match game.phase {
+        // Any player of the game can bid, other actions wait for the auction to end
+        TurnPhase::Auction => matches!(command, ClientCommand::PlaceBid(_)),
+        _ if is_trade => true,
+        // During a shortage any player can ask for a house until the requests are settled
+        _ if !game.house_requests.is_empty() && matches!(command, ClientCommand::BuildHouse(_)) => {
+            true
+        }
+        // Only the debtors can act, to raise funds or give up
+        TurnPhase::PayingDebt => {
+            is_debtor(game, uuid)
+                && matches!(
+                    command,
+                    ClientCommand::Mortgage(_)
+                        | ClientCommand::SellHouse(_)
+                        | ClientCommand::DeclareBankruptcy
+                )
+        }
+        _ if game.players[game.player_turn].id != uuid => {
+            return Err(CommandError::NotYourTurn);
+        }
+        TurnPhase::AwaitingRoll => manages_tiles || matches!(command, ClientCommand::Roll),
+        TurnPhase::InJailDecision => {
+            manages_tiles
+                || matches!(
+                    command,
+                    ClientCommand::Roll | ClientCommand::PayJailFine | ClientCommand::UseJailCard
+                )
+        }
+        TurnPhase::AwaitingMoveChoice => {
+            matches!(
+                command,
+                ClientCommand::TakeBus(_) | ClientCommand::Teleport(_)
+            )
+        }
+        TurnPhase::AwaitingTaxChoice => matches!(command, ClientCommand::ChooseIncomeTax(_)),
+        TurnPhase::AwaitingBuyDecision => {
+            manages_tiles
+                || matches!(
+                    command,
+                    ClientCommand::BuyProperty | ClientCommand::SkipBuyProperty
+                )
+        }
+        TurnPhase::TurnEnd => false,
+    };
+    if !allowed {
+        return Err(CommandError::WrongPhase(game.phase));
+    }
+    Ok(())
+}
+
+// Returns None when the command is not part of a trade
+async fn handle_trade(
+    game: &mut Game,
+    uuid: Uuid,
+    command: &ClientCommand,
+) -> Option<Result<(), TradeError>> {
+    let result = match command {
+        ClientCommand::ProposeTrade(offer) => propose_trade(game, uuid, offer.clone()).await,
+        ClientCommand::CounterTrade(offer) => counter_trade(game, uuid, offer.clone()).await,
+        ClientCommand::AcceptTrade(trade_id) => accept_trade(game, uuid, *trade_id).await,
+        ClientCommand::RejectTrade(trade_id) => reject_trade(game, uuid, *trade_id).await,
+        _ => return None,
+    };
+    Some(result)
+}
+
+// Players in the waiting room can't act until their game starts
+pub(crate) fn handle_message(message: &str) -> Result<(), CommandError> {
+    parse_command(message)?;
+    Err(CommandError::NotStarted)
+}
+
+// Developer commands are refused by name when the server is built without them
+fn parse_command(message: &str) -> Result<ClientCommand, CommandError> {
+    #[cfg(not(feature = "debug-commands"))]
+    if serde_json::from_str::<serde_json::Value>(message)
+        .is_ok_and(|value| value["command"] == "Debug")
+    {
+        return Err(CommandError::DebugDisabled);
+    }
+    serde_json::from_str(message).map_err(|e| CommandError::Malformed(e.to_string()))
 }
 
 pub(crate) async fn handle_connection(socket: TcpStream, state: Arc<ServerState>) {
@@ -76,62 +337,152 @@ pub(crate) async fn handle_connection(socket: TcpStream, state: Arc<ServerState>
     let mut reader = BufReader::new(reader);
     let mut buf = String::new();
 
-    reader.read_line(&mut buf).await.unwrap();
-    let player_action: PlayerAction = serde_json::from_str(&buf).unwrap();
+    if reader.read_line(&mut buf).await.is_err() {
+        return;
+    }
+    let identify = match check_handshake(&buf) {
+        Ok(identify) => identify,
+        Err(e) => {
+            log::debug!("Handshake rejected: {e}");
+            let event = ServerEvent::HandshakeRejected(HandshakeRejectedData {
+                protocol_version: PROTOCOL_VERSION,
+                reason: e.to_string(),
+            });
+            let _ = write_event(&mut writer, &event).await;
+            return;
+        }
+    };
+    log::debug!(
+        "Player {} joined with capabilities {:?}",
+        identify.name,
+        identify.capabilities
+    );
     buf.clear();
-    let name = player_action.data.unwrap();
 
     let (tx, mut rx) = mpsc::channel(32); // Player's message channel
-    let player = Player::default(tx, name);
+    let player = Player::default(tx, identify.name);
     let player_id = player.id;
-    send_message(&player, Action::Identify, Some(player_id.to_string())).await;
+    let welcome = WelcomeData {
+        player: player_id,
+        protocol_version: PROTOCOL_VERSION,
+        capabilities: CAPABILITIES.iter().map(|c| c.to_string()).collect(),
+    };
+    send_message(&player, ServerEvent::Welcome(welcome)).await;
 
     add_to_waiting_room(&state, player).await;
 
     // Handle client messages
     loop {
         tokio::select! {
-            Ok(len) = reader.read_line(&mut buf) => {
-                let mut waiting_room = state.waiting_room.lock().await;
-                if len == 0 {
-                    log::debug!("Player {player_id} disconnected");
-                    if waiting_room.players.iter().any(|p| p.id == player_id) {
-                        waiting_room.players.retain(|player| player.id != player_id);
-                        log::debug!("Player {} left waiting room. Total players: {}",player_id, waiting_room.players.len());
-                        log::debug!("Players {:?}", waiting_room.players);
-                    } else {
-                        let mut games = state.active_games.lock().await;
-                        for (_, game) in games.iter_mut() {
-                            let is_player_turn = game.players[game.player_turn].id == player_id;
-                            game.players.retain(|player| player.id != player_id);
-                            log::debug!("Player {player_id} left the game. Total player in the game: {}", game.players.len());
-                            if is_player_turn && !game.players.is_empty() {
-                                game.advance_turn().await;
-                            }
+            result = reader.read_line(&mut buf) => {
+                let len = match result {
+                    Ok(len) => len,
+                    // The line wasn't UTF-8, the next one can still be read
+                    Err(e) if e.kind() == ErrorKind::InvalidData => {
+                        let error = CommandError::Malformed(e.to_string());
+                        if write_event(&mut writer, &error.to_event()).await.is_err() {
+                            remove_player(&state, player_id).await;
+                            break;
                         }
-                        games.retain(|_, game| {
-                            let retain_game = !game.players.is_empty() && game.is_active;
-                            if !retain_game {
-                                log::debug!("Game ended due to player leaving");
-                            }
-                            retain_game
-                        });
+                        buf.clear();
+                        continue;
+                    }
+                    Err(e) => {
+                        log::debug!("Error reading from player {player_id}: {e}");
+                        0
                     }
+                };
+                if len == 0 {
+                    log::debug!("Player {player_id} disconnected");
+                    remove_player(&state, player_id).await;
                     break;
                 }
                 log::debug!("Received message: {}", buf.trim());
-                if waiting_room.players.iter().any(|player| player.id == player_id) {
-                    handle_message(&buf, &state, player_id).await;
+                let in_waiting_room = state
+                    .waiting_room
+                    .lock()
+                    .await
+                    .players
+                    .iter()
+                    .any(|player| player.id == player_id);
+                if in_waiting_room {
+                    if let Err(e) = handle_message(&buf) {
+                        let _ = write_event(&mut writer, &e.to_event()).await;
+                    }
                 } else {
                     handle_message_in_game(&buf, &state, player_id).await;
                 }
                 buf.clear();
             }
             Some(msg) = rx.recv() => {
-                writer.write_all(msg.as_bytes()).await.unwrap();
+                if let Err(e) = writer.write_all(msg.as_bytes()).await {
+                    log::debug!("Error writing to player {player_id}: {e}");
+                    remove_player(&state, player_id).await;
+                    break;
+                }
+            }
+        }
+    }
+}
+
+// Removes a disconnected player from the waiting room or from their game
+pub(crate) async fn remove_player(state: &Arc<ServerState>, player_id: Uuid) {
+    let mut waiting_room = state.waiting_room.lock().await;
+    if waiting_room.players.iter().any(|p| p.id == player_id) {
+        waiting_room.players.retain(|player| player.id != player_id);
+        log::debug!(
+            "Player {} left waiting room. Total players: {}",
+            player_id,
+            waiting_room.players.len()
+        );
+        log::debug!("Players {:?}", waiting_room.players);
+        return;
+    }
+    let mut games = state.active_games.lock().await;
+    for (game_id, game) in games.iter_mut() {
+        let Some(index) = game.players.iter().position(|p| p.id == player_id) else {
+            continue;
+        };
+        let is_player_turn = index == game.player_turn;
+        remove_bidder(game, player_id);
+        game.house_requests
+            .retain(|request| request.player != player_id);
+        remove_from_debts(game, player_id);
+        // The turn moves on, so an auction of the leaver's turn closes unsold
+        if is_player_turn {
+            cancel_auction(game).await;
+        }
+        game.players.remove(index);
+        log::debug!(
+            "Player {player_id} left the game. Total player in the game: {}",
+            game.players.len()
+        );
+        if game.players.is_empty() {
+            continue;
+        }
+        if index < game.player_turn {
+            game.player_turn -= 1;
+        }
+        if is_player_turn {
+            // From the player before the leaver, so the turn goes to the one after them
+            game.player_turn = (index + game.players.len() - 1) % game.players.len();
+            game.advance_turn().await;
+            // Requests that waited on the cancelled auction get their own window
+            if game.auction.is_none() && !game.house_requests.is_empty() {
+                tokio::spawn(run_house_request_timer(Arc::clone(state), *game_id));
             }
+        } else if game.phase == TurnPhase::PayingDebt {
+            // The turn may have been waiting on the leaver's debts only
+            settle_debts(game).await;
         }
     }
+    games.retain(|_, game| {
+        let retain_game = !game.players.is_empty() && game.is_active;
+        if !retain_game {
+            log::debug!("Game ended due to player leaving");
+        }
+        retain_game
+    });
 }
 
 async fn add_to_waiting_room(state: &Arc<ServerState>, player: Player) {
@@ -0,0 +1,194 @@
+use crate::action::{declare_bankrupt, move_forward};
+use crate::communication::send_to_all_players;
+use crate::dice::PendingMove;
+use crate::error::GameError;
+use crate::game_state::{Game, TurnPhase};
+use crate::jackpot::add_to_pot;
+use shared::action::{DebtData, ErrorCode, ServerEvent};
+use shared::board::Tile::Property;
+use std::fmt;
+use uuid::Uuid;
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum Payment {
+    Paid,
+    // The player has to raise funds, the turn waits for the debt to be settled
+    Debt,
+    Bankrupt,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum DebtError {
+    NoDebt,
+}
+
+impl fmt::Display for DebtError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DebtError::NoDebt => write!(f, "You have no debt"),
+        }
+    }
+}
+
+impl GameError for DebtError {
+    fn code(&self) -> ErrorCode {
+        ErrorCode::IllegalMove
+    }
+}
+
+// Money the player could raise by selling every building and mortgaging every tile
+pub(crate) fn liquidation_value(game: &Game, uuid: Uuid) -> u32 {
+    game.board
+        .iter()
+        .filter(|tile| tile.owner() == Some(uuid) && !tile.is_mortgaged())
+        .map(|tile| {
+            let buildings = match tile {
+                Property { level, costs, .. } => level.clone() as u32 * (costs[1] / 2),
+                _ => 0,
+            };
+            tile.mortgage_value().unwrap_or(0) + buildings
+        })
+        .sum()
+}
+
+pub(crate) fn is_debtor(game: &Game, uuid: Uuid) -> bool {
+    game.debts.iter().any(|debt| debt.debtor == uuid)
+}
+
+// Charge the debtor, the money goes to the creditor or to the bank when there is none
+pub(crate) async fn charge(
+    game: &mut Game,
+    debtor: Uuid,
+    creditor: Option<Uuid>,
+    amount: u32,
+) -> Payment {
+    charge_many(game, debtor, &[(creditor, amount)]).await
+}
+
+// All the payments are made at once, or all of them become debts
+pub(crate) async fn charge_many(
+    game: &mut Game,
+    debtor: Uuid,
+    payments: &[(Option<Uuid>, u32)],
+) -> Payment {
+    let total: u32 = payments.iter().map(|(_, amount)| amount).sum();
+    let money = game.players.iter().find(|p| p.id == debtor).unwrap().money;
+    if money >= total {
+        for (creditor, amount) in payments {
+            transfer(game, debtor, *creditor, *amount).await;
+        }
+        return Payment::Paid;
+    }
+    let owed: u32 = game
+        .debts
+        .iter()
+        .filter(|debt| debt.debtor == debtor)
+        .map(|debt| debt.amount)
+        .sum();
+    if money + liquidation_value(game, debtor) < owed + total {
+        log::debug!("Player {debtor} can't raise {total}, going bankrupt");
+        game.debts.retain(|debt| debt.debtor != debtor);
+        let creditors: Vec<Option<Uuid>> = payments.iter().map(|(creditor, _)| *creditor).collect();
+        declare_bankrupt(game, &debtor, single_creditor(&creditors)).await;
+        return Payment::Bankrupt;
+    }
+    for (creditor, amount) in payments {
+        let debt = DebtData {
+            debtor,
+            creditor: *creditor,
+            amount: *amount,
+        };
+        log::debug!("Player {debtor} owes {amount} to {creditor:?}");
+        send_to_all_players(&game.players, ServerEvent::DebtOutstanding(debt.clone())).await;
+        game.debts.push(debt);
+    }
+    game.phase = TurnPhase::PayingDebt;
+    Payment::Debt
+}
+
+// Assets go to the creditor only when the player owes a single player, otherwise to the bank
+fn single_creditor(creditors: &[Option<Uuid>]) -> Option<Uuid> {
+    match creditors.first() {
+        Some(first) if creditors.iter().all(|creditor| creditor == first) => *first,
+        _ => None,
+    }
+}
+
+async fn transfer(game: &mut Game, debtor: Uuid, creditor: Option<Uuid>, amount: u32) {
+    for player in game.players.iter_mut() {
+        if player.id == debtor {
+            player.money -= amount;
+        } else if Some(player.id) == creditor {
+            player.money += amount;
+        }
+    }
+    if creditor.is_none() {
+        add_to_pot(game, amount).await;
+    }
+}
+
+// The player leaves the game: their debts and what they were owed are dropped
+pub(crate) fn remove_from_debts(game: &mut Game, uuid: Uuid) {
+    game.debts
+        .retain(|debt| debt.debtor != uuid && debt.creditor != Some(uuid));
+}
+
+// Pay every debt the debtors can now afford, the turn resumes once none is left
+pub(crate) async fn settle_debts(game: &mut Game) {
+    if game.phase != TurnPhase::PayingDebt {
+        return;
+    }
+    let mut index = 0;
+    while index < game.debts.len() {
+        let debt = game.debts[index].clone();
+        // Debts of a player who left the game are dropped
+        let Some(money) = game
+            .players
+            .iter()
+            .find(|p| p.id == debt.debtor)
+            .map(|p| p.money)
+        else {
+            game.debts.remove(index);
+            continue;
+        };
+        if money < debt.amount {
+            index += 1;
+            continue;
+        }
+        transfer(game, debt.debtor, debt.creditor, debt.amount).await;
+        game.debts.remove(index);
+        log::debug!("Player {} paid their debt of {}", debt.debtor, debt.amount);
+        send_to_all_players(&game.players, ServerEvent::PayDebt(debt.clone())).await;
+    }
+    if !game.debts.is_empty() {
+        return;
+    }
+    // The roll held back by the jail fine is played now
+    if let Some(PendingMove::JailFine { steps }) = game.pending_move {
+        game.pending_move = None;
+        game.phase = TurnPhase::TurnEnd;
+        let uuid = game.players[game.player_turn].id;
+        move_forward(game, &uuid, steps as usize, steps).await;
+        return;
+    }
+    game.end_turn().await;
+}
+
+pub(crate) async fn declare_bankruptcy(game: &mut Game, uuid: Uuid) -> Result<(), DebtError> {
+    if !is_debtor(game, uuid) {
+        return Err(DebtError::NoDebt);
+    }
+    log::debug!("Player {uuid} gave up on their debts");
+    let creditors: Vec<Option<Uuid>> = game
+        .debts
+        .iter()
+        .filter(|debt| debt.debtor == uuid)
+        .map(|debt| debt.creditor)
+        .collect();
+    game.debts.retain(|debt| debt.debtor != uuid);
+    declare_bankrupt(game, &uuid, single_creditor(&creditors)).await;
+    if game.debts.is_empty() {
+        game.end_turn().await;
+    }
+    Ok(())
+}
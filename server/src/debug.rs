@@ -0,0 +1,136 @@
+use crate::cards::deck_mut;
+use crate::communication::send_to_all_players;
+use crate::dice::{DiceModel, DiceRoll};
+use crate::error::GameError;
+use crate::game_state::Game;
+use shared::action::{
+    DebugCommand, DrawCardData, ErrorCode, ForcedRollData, PlayerStartData, ServerEvent,
+    SpeedDieFace, StateRefreshData,
+};
+use shared::board::Tile::Property;
+use shared::cards::{CardDeck, CardEffect};
+use std::collections::VecDeque;
+use std::fmt;
+use uuid::Uuid;
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum DebugError {
+    InvalidTile,
+    InvalidDice,
+    NoCardLeft,
+}
+
+impl fmt::Display for DebugError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            DebugError::InvalidTile => "This tile doesn't exist",
+            DebugError::InvalidDice => "These dice can't be rolled",
+            DebugError::NoCardLeft => "The Get Out of Jail Free card of this deck is taken",
+        };
+        write!(f, "{message}")
+    }
+}
+
+impl GameError for DebugError {
+    fn code(&self) -> ErrorCode {
+        ErrorCode::IllegalMove
+    }
+}
+
+pub(crate) async fn run_debug_command(
+    game: &mut Game,
+    uuid: Uuid,
+    command: DebugCommand,
+) -> Result<(), DebugError> {
+    log::debug!("Player {uuid} runs the debug command {command:?}");
+    let index = game.players.iter().position(|p| p.id == uuid).unwrap();
+    match command {
+        DebugCommand::BuyAll => {
+            for tile in &mut game.board {
+                if let Property { owner, .. } = tile {
+                    *owner = Some(uuid);
+                }
+            }
+            send_state_refresh(game).await;
+        }
+        DebugCommand::SetMoney(money) => {
+            game.players[index].money = money;
+            send_state_refresh(game).await;
+        }
+        DebugCommand::Teleport(position) => {
+            if position >= game.board.len() {
+                return Err(DebugError::InvalidTile);
+            }
+            game.players[index].position = position;
+            // Clients move the current player on a Move event
+            if index == game.player_turn {
+                send_to_all_players(&game.players, ServerEvent::Move(position)).await;
+            }
+        }
+        DebugCommand::ForceDice(data) => force_dice(game, data)?,
+        DebugCommand::GiveCard(deck) => give_jail_card(game, index, deck).await?,
+    }
+    Ok(())
+}
+
+// Queued after the rolls already forced
+fn force_dice(game: &mut Game, data: ForcedRollData) -> Result<(), DebugError> {
+    let valid_speed_die = match data.speed_die {
+        Some(SpeedDieFace::Number(number)) => (1..=3).contains(&number),
+        _ => true,
+    };
+    if !(1..=6).contains(&data.dice1) || !(1..=6).contains(&data.dice2) || !valid_speed_die {
+        return Err(DebugError::InvalidDice);
+    }
+    let roll = DiceRoll {
+        dice1: data.dice1,
+        dice2: data.dice2,
+        speed_die: data.speed_die,
+    };
+    match &mut game.dice {
+        DiceModel::Loaded(rolls) => rolls.push_back(roll),
+        dice => *dice = DiceModel::Loaded(VecDeque::from([roll])),
+    }
+    Ok(())
+}
+
+// The card leaves its deck, as if the player drew it
+async fn give_jail_card(game: &mut Game, index: usize, deck: CardDeck) -> Result<(), DebugError> {
+    let cards = &mut deck_mut(game, deck).cards;
+    let Some(position) = cards
+        .iter()
+        .position(|card| card.effect == CardEffect::GetOutOfJailFree)
+    else {
+        return Err(DebugError::NoCardLeft);
+    };
+    let card = cards.remove(position);
+    game.players[index].jail_cards.push(deck);
+    let player = game.players[index].id;
+    send_to_all_players(
+        &game.players,
+        ServerEvent::DrawCard(DrawCardData { player, deck, card }),
+    )
+    .await;
+    Ok(())
+}
+
+async fn send_state_refresh(game: &Game) {
+    let players = game
+        .players
+        .iter()
+        .map(|p| PlayerStartData {
+            id: p.id,
+            name: p.name.clone(),
+            money: p.money,
+            position: p.position,
+        })
+        .collect();
+    send_to_all_players(
+        &game.players,
+        ServerEvent::StateRefresh(StateRefreshData {
+            players,
+            board: game.board.clone(),
+        }),
+    )
+    .await;
+}
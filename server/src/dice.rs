@@ -0,0 +1,177 @@
+use crate::action::move_forward;
+use crate::error::GameError;
+use crate::game_state::{Game, TurnPhase};
+use shared::action::{ErrorCode, SpeedDieFace};
+use shared::rules::GameRules;
+use std::fmt;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct DiceRoll {
+    pub(crate) dice1: u8,
+    pub(crate) dice2: u8,
+    pub(crate) speed_die: Option<SpeedDieFace>,
+}
+
+impl DiceRoll {
+    pub(crate) fn is_double(&self) -> bool {
+        self.dice1 == self.dice2
+    }
+
+    // The speed die shows the same number as both white dice
+    pub(crate) fn is_triple(&self) -> bool {
+        self.is_double() && self.speed_die == Some(SpeedDieFace::Number(self.dice1))
+    }
+
+    // Steps moved right away, only a number on the speed die adds to the white dice
+    pub(crate) fn total(&self) -> u8 {
+        match self.speed_die {
+            Some(SpeedDieFace::Number(number)) => self.dice1 + self.dice2 + number,
+            _ => self.dice1 + self.dice2,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum DiceModel {
+    Classic,
+    Speed,
+    // Plays the given rolls in order, then rolls two dice
+    #[cfg(any(test, feature = "debug-commands"))]
+    Loaded(std::collections::VecDeque<DiceRoll>),
+}
+
+impl DiceModel {
+    pub(crate) fn from_rules(rules: &GameRules) -> Self {
+        if rules.speed_die {
+            DiceModel::Speed
+        } else {
+            DiceModel::Classic
+        }
+    }
+
+    pub(crate) fn roll(&mut self) -> DiceRoll {
+        let dice1 = rand::random::<u8>() % 6 + 1;
+        let dice2 = rand::random::<u8>() % 6 + 1;
+        let speed_die = match self {
+            DiceModel::Classic => None,
+            DiceModel::Speed => Some(match rand::random::<u8>() % 6 {
+                0..=2 => SpeedDieFace::Number(rand::random::<u8>() % 3 + 1),
+                3 | 4 => SpeedDieFace::MrMonopoly,
+                _ => SpeedDieFace::Bus,
+            }),
+            #[cfg(any(test, feature = "debug-commands"))]
+            DiceModel::Loaded(rolls) => {
+                if let Some(roll) = rolls.pop_front() {
+                    return roll;
+                }
+                None
+            }
+        };
+        DiceRoll {
+            dice1,
+            dice2,
+            speed_die,
+        }
+    }
+}
+
+// Move the speed die leaves to the player's choice, or held back by a debt
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum PendingMove {
+    Bus { dice1: u8, dice2: u8 },
+    Teleport,
+    // Roll of the third failed jail attempt, played once the fine is paid
+    JailFine { steps: u8 },
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum DiceError {
+    NoBus,
+    NoTeleport,
+    InvalidSteps,
+    InvalidTile,
+}
+
+impl fmt::Display for DiceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DiceError::NoBus => write!(f, "You didn't roll the bus"),
+            DiceError::NoTeleport => write!(f, "You didn't roll a triple"),
+            DiceError::InvalidSteps => write!(f, "Move by one of the dice or their total"),
+            DiceError::InvalidTile => write!(f, "This tile doesn't exist"),
+        }
+    }
+}
+
+impl GameError for DiceError {
+    fn code(&self) -> ErrorCode {
+        ErrorCode::IllegalMove
+    }
+}
+
+// The bus moves the player by either white die or by both
+pub(crate) async fn take_bus(game: &mut Game, uuid: Uuid, steps: u8) -> Result<(), DiceError> {
+    let Some(PendingMove::Bus { dice1, dice2 }) = game.pending_move else {
+        return Err(DiceError::NoBus);
+    };
+    if ![dice1, dice2, dice1 + dice2].contains(&steps) {
+        return Err(DiceError::InvalidSteps);
+    }
+    game.pending_move = None;
+    game.phase = TurnPhase::TurnEnd;
+    log::debug!("Player {uuid} took the bus for {steps} steps");
+    move_forward(game, &uuid, steps as usize, steps).await;
+    Ok(())
+}
+
+// A triple moves the player forward to any tile of the board
+pub(crate) async fn teleport(game: &mut Game, uuid: Uuid, target: usize) -> Result<(), DiceError> {
+    if game.pending_move != Some(PendingMove::Teleport) {
+        return Err(DiceError::NoTeleport);
+    }
+    if target >= game.board.len() {
+        return Err(DiceError::InvalidTile);
+    }
+    game.pending_move = None;
+    game.phase = TurnPhase::TurnEnd;
+    let start = game.players[game.player_turn].position;
+    let steps = (target + game.board.len() - start) % game.board.len();
+    log::debug!("Player {uuid} teleported to {target}");
+    let roll = game.last_roll;
+    move_forward(game, &uuid, steps, roll).await;
+    Ok(())
+}
+
+// Next unowned tile ahead, or else the next one the player pays rent on
+pub(crate) fn mr_monopoly_target(game: &Game, uuid: Uuid) -> Option<usize> {
+    let start = game.players[game.player_turn].position;
+    let ahead: Vec<usize> = (1..game.board.len())
+        .map(|step| (start + step) % game.board.len())
+        .filter(|position| game.board[*position].price().is_some())
+        .collect();
+    ahead
+        .iter()
+        .find(|position| game.board[**position].owner().is_none())
+        .or_else(|| {
+            ahead.iter().find(|position| {
+                let tile = &game.board[**position];
+                tile.owner() != Some(uuid) && !tile.is_mortgaged()
+            })
+        })
+        .copied()
+}
+
+// Returns false when Mr. Monopoly has nowhere to go
+pub(crate) async fn move_mr_monopoly(game: &mut Game) -> bool {
+    let uuid = game.players[game.player_turn].id;
+    let Some(target) = mr_monopoly_target(game, uuid) else {
+        return false;
+    };
+    let start = game.players[game.player_turn].position;
+    let steps = (target + game.board.len() - start) % game.board.len();
+    log::debug!("Mr. Monopoly sends player {uuid} to {target}");
+    let roll = game.last_roll;
+    move_forward(game, &uuid, steps, roll).await;
+    true
+}
@@ -0,0 +1,14 @@
+use shared::action::{ErrorCode, ErrorData, ServerEvent};
+use std::fmt;
+
+// A refused command, sent back to the player who issued it
+pub(crate) trait GameError: fmt::Display {
+    fn code(&self) -> ErrorCode;
+
+    fn to_event(&self) -> ServerEvent {
+        ServerEvent::Error(ErrorData {
+            code: self.code(),
+            message: self.to_string(),
+        })
+    }
+}
@@ -1,10 +1,22 @@
+use crate::auction::Auction;
+use crate::building::HouseRequest;
+use crate::cards::Deck;
 use crate::communication::send_to_all_players;
+use crate::dice::{move_mr_monopoly, DiceModel, PendingMove};
 use crate::server_state::ServerState;
-use shared::action::Action;
-use shared::action::PlayerIdentifyData;
-use shared::list_const::NUMBER_PLAYERS_PER_GAME;
+use shared::action::ServerEvent;
+use shared::action::{
+    DebtData, GameOverData, GameOverReason, GameStartData, IncomeTaxData, PlayerStartData,
+    Standing, TradeOffer,
+};
+use shared::board::net_worth;
+use shared::cards::{CardDeck, CHANCE_CARDS, COMMUNITY_CHEST_CARDS};
+use shared::list_const::{BANK_HOTELS, BANK_HOUSES, NUMBER_PLAYERS_PER_GAME};
 use shared::maps::map1::MAP1;
+use shared::rules::GameRules;
+use std::cmp::Reverse;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 use uuid::Uuid;
 
@@ -18,6 +30,10 @@ pub struct Player {
     pub(crate) is_in_jail: bool,
     pub(crate) jail_turns: u8,
     pub(crate) is_bankrupt: bool,
+    // Get Out of Jail Free cards held, with the deck each one goes back to
+    pub(crate) jail_cards: Vec<CardDeck>,
+    // Doubles rolled in a row during the current turn
+    pub(crate) doubles_count: u8,
 }
 
 impl Player {
@@ -26,11 +42,13 @@ impl Player {
             id: Uuid::new_v4(),
             name,
             tx,
-            money: 1500,
+            money: GameRules::default().starting_money,
             position: 0,
             is_in_jail: false,
             jail_turns: 0,
             is_bankrupt: false,
+            jail_cards: vec![],
+            doubles_count: 0,
         }
     }
 }
@@ -40,6 +58,23 @@ pub(crate) struct WaitingRoom {
     pub(crate) players: Vec<Player>,
 }
 
+// What the game waits for, commands that don't fit the phase are refused
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum TurnPhase {
+    AwaitingRoll,
+    // The jailed player rolls for doubles, pays the fine or uses a card
+    InJailDecision,
+    // Bus or triple of the speed die
+    AwaitingMoveChoice,
+    AwaitingTaxChoice,
+    // The player was offered the tile they landed on
+    AwaitingBuyDecision,
+    Auction,
+    PayingDebt,
+    // The roll is being resolved, or the game is over
+    TurnEnd,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct Game {
     pub(crate) id: Uuid,
@@ -48,31 +83,134 @@ pub(crate) struct Game {
     pub(crate) current_turn: usize,
     pub(crate) player_turn: usize,
     pub(crate) is_active: bool,
+    pub(crate) chance_cards: Deck,
+    pub(crate) community_chest_cards: Deck,
+    pub(crate) houses_available: u32,
+    pub(crate) hotels_available: u32,
+    pub(crate) auction: Option<Auction>,
+    // Houses asked for during a shortage, settled together once the request window closes
+    pub(crate) house_requests: Vec<HouseRequest>,
+    pub(crate) trades: Vec<TradeOffer>,
+    // Payments the players could not cover yet, the turn waits until they are settled
+    pub(crate) debts: Vec<DebtData>,
+    pub(crate) rules: GameRules,
+    // Taxes and fines waiting on Free Parking
+    pub(crate) free_parking_pot: u32,
+    // Income tax waiting for the player's choice
+    pub(crate) income_tax: Option<IncomeTaxData>,
+    pub(crate) dice: DiceModel,
+    // Steps of the last roll, utilities charge rent on it
+    pub(crate) last_roll: u8,
+    // Bus or triple waiting for the player's choice
+    pub(crate) pending_move: Option<PendingMove>,
+    // Mr. Monopoly was rolled and moves the player once the tile is resolved
+    pub(crate) mr_monopoly: bool,
+    pub(crate) started_at: Instant,
+    pub(crate) phase: TurnPhase,
 }
 
 impl Game {
+    // Close the current action, the player keeps the turn after rolling doubles
+    pub(crate) async fn end_turn(&mut self) {
+        let player = &self.players[self.player_turn];
+        let can_move = !player.is_in_jail && !player.is_bankrupt;
+        if std::mem::take(&mut self.mr_monopoly)
+            && can_move
+            && Box::pin(move_mr_monopoly(self)).await
+        {
+            return;
+        }
+        let player = &self.players[self.player_turn];
+        if player.doubles_count > 0 && !player.is_in_jail && !player.is_bankrupt {
+            log::debug!("Player {} rolled doubles and rolls again", player.id);
+            self.phase = TurnPhase::AwaitingRoll;
+            return;
+        }
+        self.advance_turn().await;
+    }
+
     pub(crate) async fn advance_turn(&mut self) {
         // Check if more than one player is not bankrupt
         let number_players_left = self.players.iter().filter(|p| !p.is_bankrupt).count();
         if number_players_left == 1 {
-            let winner = self.players.iter().find(|p| !p.is_bankrupt).unwrap();
-            send_to_all_players(&self.players, Action::GameOver, Some(winner.id.to_string())).await;
-            self.is_active = false;
+            self.game_over(GameOverReason::LastPlayerStanding).await;
+            return;
+        }
+        if self
+            .rules
+            .max_turns
+            .is_some_and(|max_turns| self.current_turn + 1 >= max_turns)
+        {
+            self.game_over(GameOverReason::TurnLimit).await;
             return;
         }
+        if self
+            .rules
+            .time_limit_minutes
+            .is_some_and(|minutes| self.started_at.elapsed() >= Duration::from_secs(minutes * 60))
+        {
+            self.game_over(GameOverReason::TimeLimit).await;
+            return;
+        }
+        self.players[self.player_turn].doubles_count = 0;
+        self.pending_move = None;
+        self.mr_monopoly = false;
         self.current_turn += 1;
         self.player_turn = (self.player_turn + 1) % self.players.len();
         while self.players[self.player_turn].is_bankrupt {
             self.player_turn = (self.player_turn + 1) % self.players.len();
         }
+        self.phase = if self.players[self.player_turn].is_in_jail {
+            TurnPhase::InJailDecision
+        } else {
+            TurnPhase::AwaitingRoll
+        };
         send_to_all_players(
             &self.players,
-            Action::PlayerTurn,
-            Some(self.players[self.player_turn].id.to_string()),
+            ServerEvent::PlayerTurn(self.players[self.player_turn].id),
         )
         .await;
     }
 
+    // Players ranked by net worth, bankrupt players last
+    pub(crate) fn standings(&self) -> Vec<Standing> {
+        let mut standings: Vec<Standing> = self
+            .players
+            .iter()
+            .map(|player| Standing {
+                player: player.id,
+                money: player.money,
+                net_worth: net_worth(&self.board, player.id, player.money),
+                is_bankrupt: player.is_bankrupt,
+            })
+            .collect();
+        standings.sort_by_key(|standing| (standing.is_bankrupt, Reverse(standing.net_worth)));
+        standings
+    }
+
+    pub(crate) async fn game_over(&mut self, reason: GameOverReason) {
+        let standings = self.standings();
+        let winner = standings[0].player;
+        log::debug!(
+            "Game {} is over ({reason:?}), player {winner} wins",
+            self.id
+        );
+        send_to_all_players(
+            &self.players,
+            ServerEvent::GameOver(GameOverData {
+                winner,
+                reason,
+                standings,
+            }),
+        )
+        .await;
+        self.is_active = false;
+        self.phase = TurnPhase::TurnEnd;
+        // Nothing is left to settle once the game is over
+        self.auction = None;
+        self.house_requests.clear();
+    }
+
     pub(crate) fn default() -> Self {
         Self {
             id: Uuid::new_v4(),
@@ -81,6 +219,23 @@ impl Game {
             current_turn: 0,
             player_turn: 0,
             is_active: true,
+            chance_cards: Deck::new(&CHANCE_CARDS),
+            community_chest_cards: Deck::new(&COMMUNITY_CHEST_CARDS),
+            houses_available: BANK_HOUSES,
+            hotels_available: BANK_HOTELS,
+            auction: None,
+            house_requests: vec![],
+            trades: vec![],
+            debts: vec![],
+            rules: GameRules::default(),
+            free_parking_pot: 0,
+            income_tax: None,
+            dice: DiceModel::Classic,
+            last_roll: 0,
+            pending_move: None,
+            mr_monopoly: false,
+            started_at: Instant::now(),
+            phase: TurnPhase::AwaitingRoll,
         }
     }
 }
@@ -100,30 +255,60 @@ pub(crate) async fn start_new_game(state: Arc<ServerState>) {
 
     let mut game = Game::default();
     let game_id = game.id;
+    game.rules = state.rules.clone();
+    game.board = state.board.clone();
+    game.dice = DiceModel::from_rules(&game.rules);
     game.players = players.clone();
+    for player in game.players.iter_mut() {
+        player.money = game.rules.starting_money;
+    }
 
+    if let Some(minutes) = game.rules.time_limit_minutes {
+        tokio::spawn(run_time_limit(
+            Arc::clone(&state),
+            game_id,
+            Duration::from_secs(minutes * 60),
+        ));
+    }
     active_games.insert(game_id, game);
     log::debug!("Started a new game with ID: {game_id}");
 
     let current_game = active_games.get_mut(&game_id).unwrap();
     current_game.player_turn = rand::random::<u8>() as usize % (players.len() - 1);
-    let players_data: Vec<PlayerIdentifyData> = players
+    let players_data: Vec<PlayerStartData> = current_game
+        .players
         .iter()
-        .map(|p| PlayerIdentifyData {
+        .map(|p| PlayerStartData {
             id: p.id,
             name: p.name.clone(),
+            money: p.money,
+            position: p.position,
         })
         .collect();
     send_to_all_players(
         &players,
-        Action::GameStart,
-        Some(serde_json::to_string(&players_data).unwrap()),
+        ServerEvent::GameStart(GameStartData {
+            players: players_data,
+            rules: current_game.rules.clone(),
+            board: current_game.board.clone(),
+        }),
     )
     .await;
     send_to_all_players(
         &players,
-        Action::PlayerTurn,
-        Some(players[current_game.player_turn].id.to_string()),
+        ServerEvent::PlayerTurn(players[current_game.player_turn].id),
     )
     .await;
 }
+
+// Ends the game when its time is up, even in the middle of a turn
+pub(crate) async fn run_time_limit(state: Arc<ServerState>, game_id: Uuid, limit: Duration) {
+    tokio::time::sleep(limit).await;
+    let mut games = state.active_games.lock().await;
+    let Some(game) = games.get_mut(&game_id) else {
+        return;
+    };
+    if game.is_active {
+        game.game_over(GameOverReason::TimeLimit).await;
+    }
+}
@@ -0,0 +1,40 @@
+use shared::action::{ClientCommand, IdentifyData};
+use shared::list_const::PROTOCOL_VERSION;
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum HandshakeError {
+    NotIdentify,
+    VersionMismatch(u32),
+    EmptyName,
+}
+
+impl fmt::Display for HandshakeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HandshakeError::NotIdentify => write!(
+                f,
+                "The first message must be an Identify command with a protocol version"
+            ),
+            HandshakeError::VersionMismatch(version) => write!(
+                f,
+                "Client protocol version {version} doesn't match the server's version {PROTOCOL_VERSION}"
+            ),
+            HandshakeError::EmptyName => write!(f, "The player name can't be empty"),
+        }
+    }
+}
+
+// Checks the first line sent by a client before it joins the waiting room
+pub(crate) fn check_handshake(message: &str) -> Result<IdentifyData, HandshakeError> {
+    let Ok(ClientCommand::Identify(data)) = serde_json::from_str(message) else {
+        return Err(HandshakeError::NotIdentify);
+    };
+    if data.protocol_version != PROTOCOL_VERSION {
+        return Err(HandshakeError::VersionMismatch(data.protocol_version));
+    }
+    if data.name.trim().is_empty() {
+        return Err(HandshakeError::EmptyName);
+    }
+    Ok(data)
+}
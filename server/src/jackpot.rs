@@ -0,0 +1,38 @@
+use crate::communication::send_to_all_players;
+use crate::game_state::Game;
+use shared::action::{JackpotData, ServerEvent};
+use uuid::Uuid;
+
+async fn send_jackpot(game: &Game, collected_by: Option<Uuid>, amount: u32) {
+    send_to_all_players(
+        &game.players,
+        ServerEvent::Jackpot(JackpotData {
+            pot: game.free_parking_pot,
+            collected_by,
+            amount,
+        }),
+    )
+    .await;
+}
+
+// Money paid to the bank goes to the Free Parking pot when the rule is enabled
+pub(crate) async fn add_to_pot(game: &mut Game, amount: u32) {
+    if !game.rules.free_parking_jackpot || amount == 0 {
+        return;
+    }
+    game.free_parking_pot += amount;
+    log::debug!("Free Parking pot is now {}", game.free_parking_pot);
+    send_jackpot(game, None, amount).await;
+}
+
+pub(crate) async fn collect_pot(game: &mut Game, uuid: Uuid) {
+    if !game.rules.free_parking_jackpot || game.free_parking_pot == 0 {
+        return;
+    }
+    let amount = std::mem::take(&mut game.free_parking_pot);
+    if let Some(player) = game.players.iter_mut().find(|p| p.id == uuid) {
+        player.money += amount;
+    }
+    log::debug!("Player {uuid} collected the Free Parking pot of {amount}");
+    send_jackpot(game, Some(uuid), amount).await;
+}
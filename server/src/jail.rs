@@ -0,0 +1,96 @@
+use crate::cards::return_jail_card;
+use crate::communication::send_to_all_players;
+use crate::error::GameError;
+use crate::game_state::{Game, TurnPhase};
+use crate::jackpot::add_to_pot;
+use shared::action::{ErrorCode, JailData, ServerEvent};
+use shared::cards::CardDeck;
+use std::fmt;
+use uuid::Uuid;
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum JailError {
+    NotInJail,
+    NoJailCard,
+    NotEnoughMoney,
+}
+
+impl fmt::Display for JailError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            JailError::NotInJail => "You are not in jail",
+            JailError::NoJailCard => "You have no Get Out of Jail Free card",
+            JailError::NotEnoughMoney => "Not enough money to pay the fine",
+        };
+        write!(f, "{message}")
+    }
+}
+
+impl GameError for JailError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            JailError::NotEnoughMoney => ErrorCode::InsufficientFunds,
+            _ => ErrorCode::IllegalMove,
+        }
+    }
+}
+
+// Free the player so they can roll normally this turn
+pub(crate) fn release(game: &mut Game, uuid: Uuid) {
+    if let Some(player) = game.players.iter_mut().find(|p| p.id == uuid) {
+        player.is_in_jail = false;
+        player.jail_turns = 0;
+    }
+}
+
+pub(crate) async fn send_jail_exit(
+    game: &Game,
+    event: fn(JailData) -> ServerEvent,
+    uuid: Uuid,
+    fine: u32,
+    card: Option<CardDeck>,
+) {
+    send_to_all_players(
+        &game.players,
+        event(JailData {
+            player: uuid,
+            fine,
+            card,
+        }),
+    )
+    .await;
+}
+
+pub(crate) async fn pay_jail_fine(game: &mut Game, uuid: Uuid) -> Result<(), JailError> {
+    let fine = game.rules.jail_fine;
+    let player = game.players.iter_mut().find(|p| p.id == uuid).unwrap();
+    if !player.is_in_jail {
+        return Err(JailError::NotInJail);
+    }
+    if player.money < fine {
+        return Err(JailError::NotEnoughMoney);
+    }
+    player.money -= fine;
+    release(game, uuid);
+    add_to_pot(game, fine).await;
+    log::debug!("Player {uuid} paid the jail fine");
+    send_jail_exit(game, ServerEvent::PayJailFine, uuid, fine, None).await;
+    game.phase = TurnPhase::AwaitingRoll;
+    Ok(())
+}
+
+pub(crate) async fn use_jail_card(game: &mut Game, uuid: Uuid) -> Result<(), JailError> {
+    let player = game.players.iter_mut().find(|p| p.id == uuid).unwrap();
+    if !player.is_in_jail {
+        return Err(JailError::NotInJail);
+    }
+    let Some(deck) = player.jail_cards.pop() else {
+        return Err(JailError::NoJailCard);
+    };
+    release(game, uuid);
+    return_jail_card(game, deck);
+    log::debug!("Player {uuid} used a Get Out of Jail Free card");
+    send_jail_exit(game, ServerEvent::UseJailCard, uuid, 0, Some(deck)).await;
+    game.phase = TurnPhase::AwaitingRoll;
+    Ok(())
+}
@@ -1,18 +1,68 @@
 mod action;
+mod auction;
+mod building;
+mod cards;
 mod communication;
+mod debt;
+#[cfg(feature = "debug-commands")]
+mod debug;
+mod dice;
+mod error;
 mod game_state;
+mod handshake;
+mod jackpot;
+mod jail;
+mod mortgage;
 mod server_state;
+mod tax;
 mod test;
+mod trade;
 
 use crate::communication::handle_connection;
 use crate::server_state::ServerState;
+use shared::board::Tile;
+use shared::maps::loader::load_map;
+use shared::maps::map1::MAP1;
+use shared::rules::GameRules;
 use std::sync::Arc;
 use tokio::net::TcpListener;
 
+// Value following a `--name value` command line option
+fn arg_value(name: &str) -> Option<String> {
+    let mut args = std::env::args();
+    args.find(|arg| arg == name)?;
+    args.next()
+}
+
+// House rules from the JSON file given with --rules, the standard rules otherwise
+fn load_rules() -> GameRules {
+    let Some(path) = arg_value("--rules") else {
+        return GameRules::default();
+    };
+    let content = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Can't read rules file {path}: {e}"));
+    let rules: GameRules =
+        serde_json::from_str(&content).unwrap_or_else(|e| panic!("Invalid rules file {path}: {e}"));
+    rules
+        .validate()
+        .unwrap_or_else(|e| panic!("Invalid rules file {path}: {e}"));
+    rules
+}
+
+// Board from the JSON file given with --map, the classic board otherwise
+fn load_board() -> Vec<Tile> {
+    let Some(path) = arg_value("--map") else {
+        return MAP1.clone();
+    };
+    let content = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Can't read map file {path}: {e}"));
+    load_map(&content).unwrap_or_else(|e| panic!("Invalid map file {path}: {e}"))
+}
+
 #[tokio::main]
 async fn main() {
     env_logger::init();
-    let state = Arc::new(ServerState::new());
+    let state = Arc::new(ServerState::new(load_rules(), load_board()));
     let listener = TcpListener::bind("127.0.0.1:8080").await.unwrap();
 
     println!("Server running on 127.0.0.1:8080");
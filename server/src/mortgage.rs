@@ -0,0 +1,117 @@
+use crate::communication::send_to_all_players;
+use crate::error::GameError;
+use crate::game_state::Game;
+use shared::action::{ErrorCode, MortgageData, ServerEvent};
+use shared::board::{PropertyLevel, Tile};
+use std::fmt;
+use uuid::Uuid;
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum MortgageError {
+    NotOwned,
+    AlreadyMortgaged,
+    NotMortgaged,
+    HasBuildings,
+    NotEnoughMoney,
+}
+
+impl fmt::Display for MortgageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            MortgageError::NotOwned => "You don't own this tile",
+            MortgageError::AlreadyMortgaged => "This tile is already mortgaged",
+            MortgageError::NotMortgaged => "This tile is not mortgaged",
+            MortgageError::HasBuildings => "Sell the buildings of the color group first",
+            MortgageError::NotEnoughMoney => "Not enough money to lift the mortgage",
+        };
+        write!(f, "{message}")
+    }
+}
+
+impl GameError for MortgageError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            MortgageError::NotEnoughMoney => ErrorCode::InsufficientFunds,
+            _ => ErrorCode::IllegalMove,
+        }
+    }
+}
+
+// Buildings anywhere in the color group prevent mortgaging one of its properties
+fn group_has_buildings(game: &Game, position: usize) -> bool {
+    let Tile::Property { group, .. } = &game.board[position] else {
+        return false;
+    };
+    game.board.iter().any(|tile| {
+        matches!(tile, Tile::Property { group: tile_group, level, .. }
+            if tile_group == group && *level != PropertyLevel::None)
+    })
+}
+
+pub(crate) async fn mortgage(
+    uuid: Uuid,
+    game: &mut Game,
+    position: usize,
+) -> Result<(), MortgageError> {
+    let Some(tile) = game.board.get(position) else {
+        return Err(MortgageError::NotOwned);
+    };
+    if tile.owner() != Some(uuid) {
+        return Err(MortgageError::NotOwned);
+    }
+    if tile.is_mortgaged() {
+        return Err(MortgageError::AlreadyMortgaged);
+    }
+    if group_has_buildings(game, position) {
+        return Err(MortgageError::HasBuildings);
+    }
+    let amount = tile.mortgage_value().unwrap();
+    game.board[position].set_mortgaged(true);
+    let player = game.players.iter_mut().find(|p| p.id == uuid).unwrap();
+    player.money += amount;
+    log::debug!("Player {uuid} mortgaged tile {position} for {amount}");
+    send_to_all_players(
+        &game.players,
+        ServerEvent::Mortgage(MortgageData {
+            player: uuid,
+            position: position as u32,
+            amount,
+        }),
+    )
+    .await;
+    Ok(())
+}
+
+pub(crate) async fn unmortgage(
+    uuid: Uuid,
+    game: &mut Game,
+    position: usize,
+) -> Result<(), MortgageError> {
+    let Some(tile) = game.board.get(position) else {
+        return Err(MortgageError::NotOwned);
+    };
+    if tile.owner() != Some(uuid) {
+        return Err(MortgageError::NotOwned);
+    }
+    if !tile.is_mortgaged() {
+        return Err(MortgageError::NotMortgaged);
+    }
+    let amount = tile.unmortgage_cost().unwrap();
+    let player = game.players.iter_mut().find(|p| p.id == uuid).unwrap();
+    if player.money < amount {
+        return Err(MortgageError::NotEnoughMoney);
+    }
+    player.money -= amount;
+    game.board[position].set_mortgaged(false);
+    log::debug!("Player {uuid} lifted the mortgage on tile {position} for {amount}");
+    send_to_all_players(
+        &game.players,
+        ServerEvent::Unmortgage(MortgageData {
+            player: uuid,
+            position: position as u32,
+            amount,
+        }),
+    )
+    .await;
+    Ok(())
+}
@@ -1,4 +1,6 @@
 use crate::game_state::{Game, WaitingRoom};
+use shared::board::Tile;
+use shared::rules::GameRules;
 use std::collections::HashMap;
 use tokio::sync::Mutex;
 use uuid::Uuid;
@@ -6,13 +8,19 @@ use uuid::Uuid;
 pub(crate) struct ServerState {
     pub(crate) waiting_room: Mutex<WaitingRoom>,
     pub(crate) active_games: Mutex<HashMap<Uuid, Game>>,
+    // House rules given to every new game
+    pub(crate) rules: GameRules,
+    // Board every new game is played on
+    pub(crate) board: Vec<Tile>,
 }
 
 impl ServerState {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(rules: GameRules, board: Vec<Tile>) -> Self {
         ServerState {
             waiting_room: Mutex::new(WaitingRoom { players: vec![] }),
             active_games: Mutex::new(HashMap::new()),
+            rules,
+            board,
         }
     }
 }
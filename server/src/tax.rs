@@ -0,0 +1,92 @@
+use crate::communication::send_to_all_players;
+use crate::debt::{charge, Payment};
+use crate::error::GameError;
+use crate::game_state::{Game, TurnPhase};
+use shared::action::{ErrorCode, IncomeTaxChoice, IncomeTaxData, PlayerPayTaxData, ServerEvent};
+use shared::board::net_worth;
+use std::fmt;
+use uuid::Uuid;
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum TaxError {
+    NoTaxDue,
+}
+
+impl fmt::Display for TaxError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TaxError::NoTaxDue => write!(f, "You have no income tax to pay"),
+        }
+    }
+}
+
+impl GameError for TaxError {
+    fn code(&self) -> ErrorCode {
+        ErrorCode::IllegalMove
+    }
+}
+
+// Returns false when the player has to raise funds first, the turn then waits for the debt
+pub(crate) async fn pay_tax(game: &mut Game, uuid: Uuid, amount: u32) -> bool {
+    match charge(game, uuid, None, amount).await {
+        Payment::Paid => {
+            send_to_all_players(
+                &game.players,
+                ServerEvent::PayTax(PlayerPayTaxData {
+                    player: uuid,
+                    amount,
+                }),
+            )
+            .await;
+            true
+        }
+        Payment::Debt => false,
+        Payment::Bankrupt => true,
+    }
+}
+
+// The turn waits for the player to pick how they pay
+pub(crate) async fn ask_income_tax(game: &mut Game, uuid: Uuid, flat: u32) {
+    let data = IncomeTaxData {
+        player: uuid,
+        flat,
+        rate: game.rules.income_tax_percent,
+    };
+    log::debug!(
+        "Player {uuid} chooses between {} and {}% of their net worth",
+        data.flat,
+        data.rate
+    );
+    send_to_all_players(&game.players, ServerEvent::AskIncomeTax(data.clone())).await;
+    game.income_tax = Some(data);
+    game.phase = TurnPhase::AwaitingTaxChoice;
+}
+
+pub(crate) async fn choose_income_tax(
+    game: &mut Game,
+    uuid: Uuid,
+    choice: IncomeTaxChoice,
+) -> Result<(), TaxError> {
+    if game
+        .income_tax
+        .as_ref()
+        .is_none_or(|tax| tax.player != uuid)
+    {
+        return Err(TaxError::NoTaxDue);
+    }
+    let tax = game.income_tax.take().unwrap();
+    game.phase = TurnPhase::TurnEnd;
+    // The net worth is taken as it is now, after any change made while choosing
+    let amount = match choice {
+        IncomeTaxChoice::Flat => tax.flat,
+        IncomeTaxChoice::Percent => {
+            let player = game.players.iter().find(|p| p.id == uuid).unwrap();
+            net_worth(&game.board, uuid, player.money) * tax.rate / 100
+        }
+    };
+    log::debug!("Player {uuid} pays {amount} of income tax");
+    if pay_tax(game, uuid, amount).await {
+        game.end_turn().await;
+    }
+    Ok(())
+}
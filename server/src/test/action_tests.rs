@@ -1,6 +1,11 @@
-use crate::action::{buy_property, roll_dice};
-use crate::game_state::{Game, Player};
+use crate::action::{buy_property, land_on_tile, pass_go_tiles, roll_dice, BuyError};
+use crate::dice::{DiceModel, DiceRoll};
+use crate::game_state::{Game, Player, TurnPhase};
+use crate::test::test_player;
+use shared::action::AuctionItem;
 use shared::board::Tile;
+use shared::maps::map_go::MAP_GO;
+use std::collections::VecDeque;
 use tokio::sync::mpsc;
 use uuid::Uuid;
 
@@ -11,25 +16,19 @@ async fn roll_dice_works() {
     let player_id = Uuid::new_v4();
     let (tx, mut rx) = mpsc::channel(32);
 
-    game.players = vec![Player {
-        id: player_id,
-        name: "TestPlayer".to_owned(),
-        tx,
-        money: 1500,
-        position: 0,
-        is_in_jail: false,
-        jail_turns: 0,
-        is_bankrupt: false,
-    }];
-
-    // Initial position
-    let initial_position = game.players[0].position;
+    game.players = vec![test_player(player_id, tx)];
+    // A roll that can't land on a card sending the player back to Go
+    game.dice = DiceModel::Loaded(VecDeque::from([DiceRoll {
+        dice1: 1,
+        dice2: 2,
+        speed_die: None,
+    }]));
 
     // Execute roll dice
     roll_dice(&mut game, &player_id).await;
 
-    // Player should have moved (position changed)
-    assert_ne!(game.players[0].position, initial_position);
+    // Player should have moved by the roll
+    assert_eq!(game.players[0].position, 3);
 
     // Verify a message was sent to the player with roll data
     let message = rx.recv().await.unwrap();
@@ -46,14 +45,10 @@ async fn roll_dice_in_jail() {
     let (tx, mut rx) = mpsc::channel(32);
 
     game.players = vec![Player {
-        id: player_id,
-        name: "JailedPlayer".to_owned(),
-        tx,
-        money: 1500,
         position: 10, // Jail position
         is_in_jail: true,
         jail_turns: 3,
-        is_bankrupt: false,
+        ..test_player(player_id, tx)
     }];
 
     // Execute roll dice
@@ -81,21 +76,15 @@ async fn buy_property_works() {
     let (tx, mut rx) = mpsc::channel(32);
 
     game.players = vec![Player {
-        id: player_id,
-        name: "Buyer".to_owned(),
-        tx,
-        money: 1500,
         position: 1, // Position with property
-        is_in_jail: false,
-        jail_turns: 0,
-        is_bankrupt: false,
+        ..test_player(player_id, tx)
     }];
 
     // Get initial money
     let initial_money = game.players[0].money;
 
     // Execute buy property
-    buy_property(player_id, &mut game).await;
+    assert!(buy_property(player_id, &mut game).await.is_ok());
 
     // Player should have less money now
     assert!(game.players[0].money < initial_money);
@@ -112,3 +101,185 @@ async fn buy_property_works() {
     let msg = rx.recv().await.unwrap();
     assert!(msg.contains("BuyProperty"));
 }
+
+#[tokio::test]
+async fn buy_property_without_the_money_is_refused() {
+    let mut game = Game::default();
+    let player_id = Uuid::new_v4();
+    let (tx, _rx) = mpsc::channel(32);
+
+    game.players = vec![Player {
+        position: 39, // Boardwalk
+        money: 100,
+        ..test_player(player_id, tx)
+    }];
+    game.phase = TurnPhase::AwaitingBuyDecision;
+
+    assert_eq!(
+        buy_property(player_id, &mut game).await,
+        Err(BuyError::NotEnoughMoney)
+    );
+    // The player can still decline and send the tile to auction
+    assert_eq!(game.phase, TurnPhase::AwaitingBuyDecision);
+    assert_eq!(game.players[0].money, 100);
+    assert_eq!(game.board[39].owner(), None);
+}
+
+#[tokio::test]
+async fn monopoly_doubles_unimproved_rent() {
+    let mut game = Game::default();
+    let player_id = Uuid::new_v4();
+    let owner_id = Uuid::new_v4();
+    let (tx1, _rx1) = mpsc::channel(32);
+    let (tx2, _rx2) = mpsc::channel(32);
+
+    game.players = vec![
+        Player {
+            position: 3, // Baltic Avenue
+            ..test_player(player_id, tx1)
+        },
+        test_player(owner_id, tx2),
+    ];
+
+    // Owner holds only Baltic Avenue: base rent
+    if let Tile::Property { owner, .. } = &mut game.board[3] {
+        *owner = Some(owner_id);
+    }
+    land_on_tile(&mut game, &player_id, 7).await;
+    assert_eq!(game.players[0].money, 1496);
+
+    // Owner holds both browns: rent is doubled
+    if let Tile::Property { owner, .. } = &mut game.board[1] {
+        *owner = Some(owner_id);
+    }
+    game.player_turn = 0;
+    land_on_tile(&mut game, &player_id, 7).await;
+    assert_eq!(game.players[0].money, 1488);
+    assert_eq!(game.players[1].money, 1512);
+}
+
+#[tokio::test]
+async fn railroad_rent_follows_railroads_owned() {
+    let mut game = Game::default();
+    let player_id = Uuid::new_v4();
+    let owner_id = Uuid::new_v4();
+    let (tx1, _rx1) = mpsc::channel(32);
+    let (tx2, _rx2) = mpsc::channel(32);
+
+    game.players = vec![
+        Player {
+            position: 5, // Reading Railroad
+            ..test_player(player_id, tx1)
+        },
+        test_player(owner_id, tx2),
+    ];
+
+    // One railroad owned: first rent
+    game.board[5].set_owner(Some(owner_id));
+    land_on_tile(&mut game, &player_id, 7).await;
+    assert_eq!(game.players[0].money, 1475);
+
+    // All four railroads owned: last rent
+    for position in [15, 25, 35] {
+        game.board[position].set_owner(Some(owner_id));
+    }
+    game.player_turn = 0;
+    land_on_tile(&mut game, &player_id, 7).await;
+    assert_eq!(game.players[0].money, 1275);
+    assert_eq!(game.players[1].money, 1725);
+}
+
+#[tokio::test]
+async fn third_doubles_send_to_jail() {
+    let mut game = Game::default();
+    let player_id = Uuid::new_v4();
+    let (tx, _) = mpsc::channel(32);
+    game.players = vec![
+        Player {
+            doubles_count: 2,
+            ..test_player(player_id, tx.clone())
+        },
+        test_player(Uuid::new_v4(), tx),
+    ];
+    game.dice = DiceModel::Loaded(VecDeque::from([DiceRoll {
+        dice1: 3,
+        dice2: 3,
+        speed_die: None,
+    }]));
+
+    roll_dice(&mut game, &player_id).await;
+
+    assert!(game.players[0].is_in_jail);
+    assert_eq!(game.players[0].position, 10);
+    assert_eq!(game.players[0].doubles_count, 0);
+    assert_eq!(game.player_turn, 1);
+}
+
+#[tokio::test]
+async fn passing_go_pays_salary() {
+    let mut game = Game::default();
+    let (tx, _) = mpsc::channel(32);
+    game.players = vec![Player {
+        position: 38,
+        ..test_player(Uuid::new_v4(), tx)
+    }];
+
+    // From 38 to 2 wraps past Go
+    pass_go_tiles(&mut game, 38, 4).await;
+    assert_eq!(game.players[0].money, 1700);
+
+    // Landing on Go is not passing it
+    pass_go_tiles(&mut game, 38, 2).await;
+    assert_eq!(game.players[0].money, 1700);
+
+    // Every Go tile passed pays its own amount
+    game.board = MAP_GO.clone();
+    pass_go_tiles(&mut game, 0, 3).await;
+    assert_eq!(game.players[0].money, 2100);
+}
+
+#[tokio::test]
+async fn go_salary_follows_rules() {
+    let mut game = Game::default();
+    let (tx, _) = mpsc::channel(32);
+    game.players = vec![test_player(Uuid::new_v4(), tx)];
+    game.rules.go_salary = Some(400);
+    game.rules.double_go_on_landing = true;
+
+    pass_go_tiles(&mut game, 38, 4).await;
+    assert_eq!(game.players[0].money, 1900);
+
+    let player_id = game.players[0].id;
+    land_on_tile(&mut game, &player_id, 2).await;
+    assert_eq!(game.players[0].money, 2700);
+}
+
+#[tokio::test]
+async fn unaffordable_tile_is_auctioned() {
+    let mut game = Game::default();
+    let player_id = Uuid::new_v4();
+    let (tx, _) = mpsc::channel(32);
+    game.players = vec![
+        Player {
+            position: 39,
+            money: 100,
+            ..test_player(player_id, tx.clone())
+        },
+        test_player(Uuid::new_v4(), tx),
+    ];
+
+    land_on_tile(&mut game, &player_id, 5).await;
+
+    let auction = game.auction.as_ref().unwrap();
+    assert_eq!(auction.item, AuctionItem::Property(39));
+    assert_eq!(auction.bidders.len(), 2);
+    assert_eq!(game.phase, TurnPhase::Auction);
+
+    // Without mandatory auctions the tile stays with the bank
+    game.auction = None;
+    game.phase = TurnPhase::TurnEnd;
+    game.rules.mandatory_auctions = false;
+    land_on_tile(&mut game, &player_id, 5).await;
+    assert!(game.auction.is_none());
+    assert_eq!(game.player_turn, 1);
+}
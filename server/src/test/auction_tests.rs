@@ -0,0 +1,161 @@
+use crate::auction::{end_auction, place_bid, start_auction, AuctionError};
+use crate::building::HouseRequest;
+use crate::game_state::{Game, Player, TurnPhase};
+use crate::test::test_player;
+use shared::action::AuctionItem;
+use shared::board::{PropertyLevel, Tile};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+fn test_game() -> (Game, Uuid, Uuid) {
+    let mut game = Game::default();
+    let first_id = Uuid::new_v4();
+    let second_id = Uuid::new_v4();
+    let (tx1, _) = mpsc::channel(32);
+    let (tx2, _) = mpsc::channel(32);
+    game.players = vec![
+        test_player(first_id, tx1),
+        Player {
+            money: 100,
+            ..test_player(second_id, tx2)
+        },
+    ];
+    (game, first_id, second_id)
+}
+
+#[tokio::test]
+async fn place_bid_checks_minimum_and_money() {
+    let (mut game, first_id, second_id) = test_game();
+    start_auction(
+        &mut game,
+        AuctionItem::House,
+        vec![first_id, second_id],
+        50,
+        vec![],
+    )
+    .await;
+
+    assert_eq!(
+        place_bid(&mut game, first_id, 40).await,
+        Err(AuctionError::BidTooLow(50))
+    );
+    assert!(place_bid(&mut game, first_id, 50).await.is_ok());
+    assert_eq!(
+        place_bid(&mut game, second_id, 55).await,
+        Err(AuctionError::BidTooLow(60))
+    );
+    assert_eq!(
+        place_bid(&mut game, second_id, 200).await,
+        Err(AuctionError::NotEnoughMoney)
+    );
+    assert_eq!(
+        place_bid(&mut game, Uuid::new_v4(), 500).await,
+        Err(AuctionError::NotABidder)
+    );
+    assert_eq!(
+        game.auction.as_ref().unwrap().highest_bidder,
+        Some(first_id)
+    );
+}
+
+#[tokio::test]
+async fn house_auction_winner_gets_the_house() {
+    let (mut game, first_id, second_id) = test_game();
+    for position in [1, 3] {
+        if let Tile::Property { owner, .. } = &mut game.board[position] {
+            *owner = Some(first_id);
+        }
+    }
+    game.houses_available = 1;
+    game.phase = TurnPhase::AwaitingBuyDecision;
+    start_auction(
+        &mut game,
+        AuctionItem::House,
+        vec![first_id, second_id],
+        50,
+        vec![HouseRequest {
+            player: first_id,
+            position: 3,
+        }],
+    )
+    .await;
+    assert_eq!(game.phase, TurnPhase::Auction);
+    assert!(place_bid(&mut game, first_id, 80).await.is_ok());
+
+    end_auction(&mut game).await;
+
+    // The turn goes back to the buy decision it was in
+    assert_eq!(game.phase, TurnPhase::AwaitingBuyDecision);
+    assert!(game.auction.is_none());
+    assert_eq!(game.houses_available, 0);
+    assert_eq!(game.players[0].money, 1420);
+    assert!(matches!(
+        game.board[3],
+        Tile::Property {
+            level: PropertyLevel::House1,
+            ..
+        }
+    ));
+}
+
+#[tokio::test]
+async fn property_auction_transfers_tile_and_ends_turn() {
+    let (mut game, first_id, second_id) = test_game();
+    game.player_turn = 0;
+    start_auction(
+        &mut game,
+        AuctionItem::Property(1),
+        vec![first_id, second_id],
+        10,
+        vec![],
+    )
+    .await;
+    assert!(place_bid(&mut game, first_id, 10).await.is_ok());
+    assert!(place_bid(&mut game, second_id, 40).await.is_ok());
+
+    end_auction(&mut game).await;
+
+    assert_eq!(game.board[1].owner(), Some(second_id));
+    assert_eq!(game.players[1].money, 60);
+    assert_eq!(game.players[0].money, 1500);
+    assert_eq!(game.player_turn, 1);
+}
+
+#[tokio::test]
+async fn property_auction_without_bids_keeps_tile_unowned() {
+    let (mut game, first_id, second_id) = test_game();
+    start_auction(
+        &mut game,
+        AuctionItem::Property(1),
+        vec![first_id, second_id],
+        10,
+        vec![],
+    )
+    .await;
+
+    end_auction(&mut game).await;
+
+    assert_eq!(game.board[1].owner(), None);
+    assert_eq!(game.player_turn, 1);
+}
+
+#[tokio::test]
+async fn property_auction_winner_who_left_buys_nothing() {
+    let (mut game, first_id, second_id) = test_game();
+    start_auction(
+        &mut game,
+        AuctionItem::Property(1),
+        vec![first_id, second_id],
+        10,
+        vec![],
+    )
+    .await;
+    assert!(place_bid(&mut game, second_id, 40).await.is_ok());
+    game.players.pop();
+
+    end_auction(&mut game).await;
+
+    assert_eq!(game.board[1].owner(), None);
+    assert!(game.auction.is_none());
+    assert_ne!(game.phase, TurnPhase::Auction);
+}
@@ -0,0 +1,201 @@
+use crate::auction::{end_auction, place_bid, start_auction};
+use crate::building::{build_house, sell_house, settle_house_requests, BuildError};
+use crate::game_state::{Game, TurnPhase};
+use crate::test::test_player;
+use shared::action::AuctionItem;
+use shared::board::{PropertyLevel, Tile};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+fn give_tiles(game: &mut Game, player_id: Uuid, owned: &[usize]) {
+    for position in owned {
+        if let Tile::Property { owner, .. } = &mut game.board[*position] {
+            *owner = Some(player_id);
+        }
+    }
+}
+
+fn game_with_owner(owned: &[usize]) -> (Game, Uuid, mpsc::Receiver<String>) {
+    let mut game = Game::default();
+    let player_id = Uuid::new_v4();
+    let (tx, rx) = mpsc::channel(32);
+    game.players = vec![test_player(player_id, tx)];
+    give_tiles(&mut game, player_id, owned);
+    (game, player_id, rx)
+}
+
+fn level_at(game: &Game, position: usize) -> PropertyLevel {
+    match &game.board[position] {
+        Tile::Property { level, .. } => level.clone(),
+        _ => panic!("Tile {position} is not a property"),
+    }
+}
+
+#[tokio::test]
+async fn build_house_requires_monopoly() {
+    let (mut game, player_id, _rx) = game_with_owner(&[1]);
+
+    let result = build_house(player_id, &mut game, 1).await;
+
+    assert_eq!(result, Err(BuildError::NoMonopoly));
+    assert_eq!(level_at(&game, 1), PropertyLevel::None);
+    assert_eq!(game.players[0].money, 1500);
+}
+
+#[tokio::test]
+async fn build_house_follows_even_build_rule() {
+    let (mut game, player_id, mut rx) = game_with_owner(&[1, 3]);
+
+    assert!(build_house(player_id, &mut game, 1).await.is_ok());
+    assert_eq!(level_at(&game, 1), PropertyLevel::House1);
+    assert_eq!(game.players[0].money, 1450);
+    assert!(rx.recv().await.unwrap().contains("BuildHouse"));
+
+    // Baltic Avenue has no house yet, so Mediterranean can't get a second one
+    let result = build_house(player_id, &mut game, 1).await;
+    assert_eq!(result, Err(BuildError::UnevenBuild));
+
+    assert!(build_house(player_id, &mut game, 3).await.is_ok());
+    assert!(build_house(player_id, &mut game, 1).await.is_ok());
+    assert_eq!(level_at(&game, 1), PropertyLevel::House2);
+    assert_eq!(level_at(&game, 3), PropertyLevel::House1);
+}
+
+#[tokio::test]
+async fn sell_house_refunds_half() {
+    let (mut game, player_id, _rx) = game_with_owner(&[1, 3]);
+    assert!(build_house(player_id, &mut game, 1).await.is_ok());
+    assert!(build_house(player_id, &mut game, 3).await.is_ok());
+    assert!(build_house(player_id, &mut game, 3).await.is_ok());
+
+    // Mediterranean Avenue is behind Baltic Avenue and can't be sold first
+    let result = sell_house(player_id, &mut game, 1).await;
+    assert_eq!(result, Err(BuildError::UnevenBuild));
+
+    assert!(sell_house(player_id, &mut game, 3).await.is_ok());
+    assert_eq!(level_at(&game, 3), PropertyLevel::House1);
+    assert_eq!(game.players[0].money, 1500 - 150 + 25);
+    assert_eq!(game.houses_available, 30);
+}
+
+#[tokio::test]
+async fn build_fails_when_bank_is_empty() {
+    let (mut game, player_id, _rx) = game_with_owner(&[1, 3]);
+    game.houses_available = 0;
+
+    let result = build_house(player_id, &mut game, 1).await;
+
+    assert_eq!(result, Err(BuildError::NoHousesLeft));
+    assert_eq!(level_at(&game, 1), PropertyLevel::None);
+}
+
+#[tokio::test]
+async fn hotel_returns_houses_to_bank() {
+    let (mut game, player_id, _rx) = game_with_owner(&[1, 3]);
+    if let Tile::Property { level, .. } = &mut game.board[1] {
+        *level = PropertyLevel::House4;
+    }
+    if let Tile::Property { level, .. } = &mut game.board[3] {
+        *level = PropertyLevel::House4;
+    }
+    game.houses_available = 24;
+
+    assert!(build_house(player_id, &mut game, 1).await.is_ok());
+
+    assert_eq!(level_at(&game, 1), PropertyLevel::Hotel);
+    assert_eq!(game.houses_available, 28);
+    assert_eq!(game.hotels_available, 11);
+}
+
+// The first player owns the browns, the rival the dark blues, and one house is left
+fn game_with_shortage() -> (Game, Uuid, Uuid) {
+    let (mut game, player_id, _) = game_with_owner(&[1, 3]);
+    let rival_id = Uuid::new_v4();
+    let (tx, _) = mpsc::channel(32);
+    game.players.push(test_player(rival_id, tx));
+    give_tiles(&mut game, rival_id, &[37, 39]);
+    game.houses_available = 1;
+    (game, player_id, rival_id)
+}
+
+#[tokio::test]
+async fn single_request_in_a_shortage_is_built() {
+    let (mut game, player_id, _) = game_with_shortage();
+
+    assert!(build_house(player_id, &mut game, 1).await.is_ok());
+    // Nothing is built until the other players had their chance to ask
+    assert_eq!(level_at(&game, 1), PropertyLevel::None);
+    assert_eq!(game.house_requests.len(), 1);
+
+    settle_house_requests(&mut game).await;
+    assert!(game.auction.is_none());
+    assert_eq!(level_at(&game, 1), PropertyLevel::House1);
+    assert_eq!(game.players[0].money, 1450);
+}
+
+#[tokio::test]
+async fn competing_requests_start_an_auction() {
+    let (mut game, player_id, rival_id) = game_with_shortage();
+
+    assert!(build_house(player_id, &mut game, 1).await.is_ok());
+    assert!(build_house(rival_id, &mut game, 37).await.is_ok());
+    settle_house_requests(&mut game).await;
+
+    let auction = game.auction.as_ref().unwrap();
+    assert_eq!(auction.bidders, vec![player_id, rival_id]);
+    assert_eq!(auction.highest_bid, 50);
+
+    // The house goes on the tile the winner asked for
+    assert!(place_bid(&mut game, rival_id, 210).await.is_ok());
+    end_auction(&mut game).await;
+    assert_eq!(level_at(&game, 37), PropertyLevel::House1);
+    assert_eq!(level_at(&game, 1), PropertyLevel::None);
+    assert_eq!(game.players[1].money, 1290);
+    assert!(game.house_requests.is_empty());
+}
+
+#[tokio::test]
+async fn house_requests_wait_for_a_running_property_auction() {
+    let (mut game, player_id, rival_id) = game_with_shortage();
+
+    assert!(build_house(player_id, &mut game, 1).await.is_ok());
+    assert!(build_house(rival_id, &mut game, 37).await.is_ok());
+    game.phase = TurnPhase::AwaitingBuyDecision;
+    start_auction(
+        &mut game,
+        AuctionItem::Property(5),
+        vec![player_id, rival_id],
+        0,
+        Vec::new(),
+    )
+    .await;
+
+    // The property auction is left alone
+    settle_house_requests(&mut game).await;
+    let auction = game.auction.as_ref().unwrap();
+    assert_eq!(auction.item, AuctionItem::Property(5));
+    assert_eq!(game.house_requests.len(), 2);
+
+    // Its end opens the house auction, which resumes the next turn
+    end_auction(&mut game).await;
+    let auction = game.auction.as_ref().unwrap();
+    assert_eq!(auction.item, AuctionItem::House);
+    assert_eq!(auction.resume_phase, TurnPhase::AwaitingRoll);
+    assert_eq!(game.phase, TurnPhase::Auction);
+    assert_eq!(game.player_turn, 1);
+
+    end_auction(&mut game).await;
+    assert!(game.auction.is_none());
+    assert_eq!(game.phase, TurnPhase::AwaitingRoll);
+}
+
+#[tokio::test]
+async fn even_build_can_be_disabled() {
+    let (mut game, player_id, _rx) = game_with_owner(&[1, 3]);
+    game.rules.even_build = false;
+
+    assert!(build_house(player_id, &mut game, 1).await.is_ok());
+    assert!(build_house(player_id, &mut game, 1).await.is_ok());
+    assert_eq!(level_at(&game, 1), PropertyLevel::House2);
+    assert_eq!(level_at(&game, 3), PropertyLevel::None);
+}
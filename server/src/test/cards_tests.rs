@@ -0,0 +1,119 @@
+use crate::cards::{draw_card, Deck};
+use crate::game_state::{Game, Player};
+use crate::test::test_player;
+use shared::board::Tile;
+use shared::cards::{Card, CardDeck, CardEffect, CardTarget, CHANCE_CARDS};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+#[test]
+fn deck_draw_cycles_through_cards() {
+    let mut deck = Deck::new(&CHANCE_CARDS);
+    assert_eq!(deck.cards.len(), CHANCE_CARDS.len());
+
+    // Drawing a full deck returns every card once, then starts over
+    let first = deck.draw();
+    for _ in 1..CHANCE_CARDS.len() {
+        assert_ne!(deck.draw(), first);
+    }
+    assert_eq!(deck.draw(), first);
+}
+
+#[tokio::test]
+async fn draw_card_collect_money() {
+    let mut game = Game::default();
+    let player_id = Uuid::new_v4();
+    let (tx, mut rx) = mpsc::channel(32);
+    game.players = vec![Player {
+        position: 7,
+        ..test_player(player_id, tx)
+    }];
+    game.chance_cards.cards = vec![Card {
+        description: "Bank pays you dividend of 50".to_owned(),
+        effect: CardEffect::Collect(50),
+    }];
+
+    let moved = draw_card(&mut game, &player_id, CardDeck::Chance, 7).await;
+
+    assert!(!moved);
+    assert_eq!(game.players[0].money, 1550);
+    let msg = rx.recv().await.unwrap();
+    assert!(msg.contains("DrawCard"));
+}
+
+#[tokio::test]
+async fn draw_card_pay_each_player() {
+    let mut game = Game::default();
+    let player_id = Uuid::new_v4();
+    let (tx1, _rx1) = mpsc::channel(32);
+    let (tx2, _rx2) = mpsc::channel(32);
+    let (tx3, _rx3) = mpsc::channel(32);
+    game.players = vec![
+        Player {
+            position: 7,
+            ..test_player(player_id, tx1)
+        },
+        test_player(Uuid::new_v4(), tx2),
+        test_player(Uuid::new_v4(), tx3),
+    ];
+    game.chance_cards.cards = vec![Card {
+        description: "You have been elected Chairman of the Board".to_owned(),
+        effect: CardEffect::PayEachPlayer(50),
+    }];
+
+    draw_card(&mut game, &player_id, CardDeck::Chance, 7).await;
+
+    assert_eq!(game.players[0].money, 1400);
+    assert_eq!(game.players[1].money, 1550);
+    assert_eq!(game.players[2].money, 1550);
+}
+
+#[tokio::test]
+async fn draw_card_move_back_lands_on_tile() {
+    let mut game = Game::default();
+    let player_id = Uuid::new_v4();
+    let (tx1, _rx1) = mpsc::channel(32);
+    let (tx2, _rx2) = mpsc::channel(32);
+    game.players = vec![
+        Player {
+            position: 7,
+            ..test_player(player_id, tx1)
+        },
+        test_player(Uuid::new_v4(), tx2),
+    ];
+    game.chance_cards.cards = vec![Card {
+        description: "Go back 3 spaces".to_owned(),
+        effect: CardEffect::MoveBack(3),
+    }];
+
+    let moved = draw_card(&mut game, &player_id, CardDeck::Chance, 7).await;
+
+    // Position 4 is the income tax tile
+    assert!(moved);
+    assert_eq!(game.players[0].position, 4);
+    assert_eq!(game.players[0].money, 1300);
+    assert_eq!(game.player_turn, 1);
+}
+
+#[tokio::test]
+async fn draw_card_move_to_finds_the_tile_by_name() {
+    let mut game = Game::default();
+    let player_id = Uuid::new_v4();
+    let (tx, _rx) = mpsc::channel(32);
+    game.players = vec![Player {
+        position: 7,
+        ..test_player(player_id, tx)
+    }];
+    // Boardwalk swapped with Park Place on this board
+    game.board.swap(37, 39);
+    game.chance_cards.cards = vec![Card {
+        description: "Advance to Boardwalk".to_owned(),
+        effect: CardEffect::MoveTo(CardTarget::Property("Boardwalk".to_owned())),
+    }];
+
+    let moved = draw_card(&mut game, &player_id, CardDeck::Chance, 7).await;
+
+    assert!(moved);
+    assert_eq!(game.players[0].position, 37);
+    assert!(matches!(&game.board[37], Tile::Property { name, .. } if name == "Boardwalk"));
+}
@@ -1,12 +1,21 @@
-use shared::action::{Action, PlayerAction};
-use std::collections::HashMap;
+use crate::test::test_player;
+use shared::action::{ClientCommand, DiceRollData, ErrorCode, ServerEvent};
+use shared::board::Tile;
+use shared::maps::map1::MAP1;
+use shared::rules::GameRules;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use tokio::sync::{mpsc, Mutex};
 use uuid::Uuid;
 
 use crate::{
-    communication::{handle_message_in_game, send_message, send_to_all_players},
-    game_state::{Game, Player, WaitingRoom},
+    communication::{
+        handle_message, handle_message_in_game, remove_player, send_message, send_to_all_players,
+        CommandError,
+    },
+    debt::{charge, Payment},
+    dice::{DiceModel, DiceRoll},
+    game_state::{Game, Player, TurnPhase, WaitingRoom},
     server_state::ServerState,
 };
 
@@ -14,27 +23,20 @@ use crate::{
 async fn send_message_works() {
     // Create a mock player with a channel
     let (tx, mut rx) = mpsc::channel(32);
-    let player = Player {
-        id: Uuid::new_v4(),
-        name: "TestPlayer".to_owned(),
-        tx,
-        money: 1500,
-        position: 0,
-        is_in_jail: false,
-        jail_turns: 0,
-        is_bankrupt: false,
-    };
+    let player = test_player(Uuid::new_v4(), tx);
 
     // Send a test message
-    send_message(&player, Action::Roll, Some("test_data".to_owned())).await;
+    let event = ServerEvent::Roll(DiceRollData {
+        dice1: 2,
+        dice2: 5,
+        extra_roll: false,
+        speed_die: None,
+    });
+    send_message(&player, event.clone()).await;
 
     // Verify the message was sent correctly
     let received_msg = rx.recv().await.unwrap();
-    let expected_action = PlayerAction {
-        action_type: Action::Roll,
-        data: Some("test_data".to_owned()),
-    };
-    let mut expected_msg = serde_json::to_string(&expected_action).unwrap();
+    let mut expected_msg = serde_json::to_string(&event).unwrap();
     expected_msg.push('\n');
     assert_eq!(received_msg, expected_msg);
 }
@@ -46,37 +48,16 @@ async fn send_to_all_players_works() {
     let (tx2, mut rx2) = mpsc::channel(32);
 
     let players = vec![
-        Player {
-            id: Uuid::new_v4(),
-            name: "Player1".to_owned(),
-            tx: tx1,
-            money: 1500,
-            position: 0,
-            is_in_jail: false,
-            jail_turns: 0,
-            is_bankrupt: false,
-        },
-        Player {
-            id: Uuid::new_v4(),
-            name: "Player2".to_owned(),
-            tx: tx2,
-            money: 1500,
-            position: 0,
-            is_in_jail: false,
-            jail_turns: 0,
-            is_bankrupt: false,
-        },
+        test_player(Uuid::new_v4(), tx1),
+        test_player(Uuid::new_v4(), tx2),
     ];
 
     // Send a message to all players
-    send_to_all_players(&players, Action::GameStart, Some("game_started".to_owned())).await;
+    let event = ServerEvent::PlayerTurn(players[0].id);
+    send_to_all_players(&players, event.clone()).await;
 
     // Verify both players received the message
-    let expected_action = PlayerAction {
-        action_type: Action::GameStart,
-        data: Some("game_started".to_owned()),
-    };
-    let mut expected_msg = serde_json::to_string(&expected_action).unwrap();
+    let mut expected_msg = serde_json::to_string(&event).unwrap();
     expected_msg.push('\n');
 
     assert_eq!(rx1.recv().await.unwrap(), expected_msg);
@@ -89,16 +70,7 @@ async fn handle_message_in_game_roll_action() {
     let player_id = Uuid::new_v4();
     let (tx, _rx) = mpsc::channel(32);
     let mut game = Game::default();
-    game.players = vec![Player {
-        id: player_id,
-        name: "TestPlayer".to_owned(),
-        tx,
-        money: 1500,
-        position: 0,
-        is_in_jail: false,
-        jail_turns: 0,
-        is_bankrupt: false,
-    }];
+    game.players = vec![test_player(player_id, tx)];
 
     // Create a mock server state
     let mut active_games = HashMap::new();
@@ -106,14 +78,12 @@ async fn handle_message_in_game_roll_action() {
     let state = Arc::new(ServerState {
         waiting_room: Mutex::new(WaitingRoom { players: vec![] }),
         active_games: Mutex::new(active_games),
+        rules: GameRules::default(),
+        board: MAP1.clone(),
     });
 
-    // Create a roll action message
-    let action = PlayerAction {
-        action_type: Action::Roll,
-        data: None,
-    };
-    let message = serde_json::to_string(&action).unwrap();
+    // Create a roll command message
+    let message = serde_json::to_string(&ClientCommand::Roll).unwrap();
 
     // Handle the message
     handle_message_in_game(&message, &state, player_id).await;
@@ -122,3 +92,320 @@ async fn handle_message_in_game_roll_action() {
     // but we can verify the game still exists
     assert!(state.active_games.lock().await.contains_key(&game.id));
 }
+
+#[tokio::test]
+async fn handle_message_in_game_skip_starts_auction() {
+    let player_id = Uuid::new_v4();
+    let (tx, _rx) = mpsc::channel(32);
+    let mut game = Game::default();
+    game.players = vec![Player {
+        position: 1,
+        ..test_player(player_id, tx)
+    }];
+    game.phase = TurnPhase::AwaitingBuyDecision;
+
+    let mut active_games = HashMap::new();
+    active_games.insert(game.id, game.clone());
+    let state = Arc::new(ServerState {
+        waiting_room: Mutex::new(WaitingRoom { players: vec![] }),
+        active_games: Mutex::new(active_games),
+        rules: GameRules::default(),
+        board: MAP1.clone(),
+    });
+
+    let message = serde_json::to_string(&ClientCommand::SkipBuyProperty).unwrap();
+    handle_message_in_game(&message, &state, player_id).await;
+
+    // The declined property is now up for auction
+    let games = state.active_games.lock().await;
+    let auction = games.get(&game.id).unwrap().auction.as_ref().unwrap();
+    assert_eq!(auction.bidders, vec![player_id]);
+}
+
+// A game where the second player is waiting for their turn, with each player's messages
+fn game_with_two_players() -> (Arc<ServerState>, Vec<(Uuid, mpsc::Receiver<String>)>) {
+    let mut game = Game::default();
+    let mut players = vec![];
+    for name in ["Player1", "Player2"] {
+        let (tx, rx) = mpsc::channel(32);
+        let player = Player::default(tx, name.to_owned());
+        players.push((player.id, rx));
+        game.players.push(player);
+    }
+    let state = Arc::new(ServerState {
+        waiting_room: Mutex::new(WaitingRoom { players: vec![] }),
+        active_games: Mutex::new(HashMap::from([(game.id, game)])),
+        rules: GameRules::default(),
+        board: MAP1.clone(),
+    });
+    (state, players)
+}
+
+// Code of the first error among the messages received so far
+fn next_error(rx: &mut mpsc::Receiver<String>) -> ErrorCode {
+    while let Ok(message) = rx.try_recv() {
+        if let ServerEvent::Error(data) = serde_json::from_str(&message).unwrap() {
+            return data.code;
+        }
+    }
+    panic!("Expected an error");
+}
+
+async fn send(state: &Arc<ServerState>, uuid: Uuid, command: ClientCommand) {
+    let message = serde_json::to_string(&command).unwrap();
+    handle_message_in_game(&message, state, uuid).await;
+}
+
+#[tokio::test]
+async fn malformed_message_is_answered_with_an_error() {
+    let (state, mut players) = game_with_two_players();
+    let (second, rx) = &mut players[1];
+
+    handle_message_in_game("{not json", &state, *second).await;
+
+    assert_eq!(next_error(rx), ErrorCode::MalformedMessage);
+    assert_eq!(state.active_games.lock().await.len(), 1);
+}
+
+#[tokio::test]
+async fn out_of_turn_command_is_answered_with_an_error() {
+    let (state, mut players) = game_with_two_players();
+    let first = players[0].0;
+    let (second, rx) = &mut players[1];
+
+    send(&state, *second, ClientCommand::Roll).await;
+
+    assert_eq!(next_error(rx), ErrorCode::NotYourTurn);
+    let games = state.active_games.lock().await;
+    let game = games.values().next().unwrap();
+    assert_eq!(game.players[game.player_turn].id, first);
+}
+
+#[tokio::test]
+async fn finished_game_refuses_every_command() {
+    let (state, mut players) = game_with_two_players();
+    let first = players[0].0;
+    {
+        let mut games = state.active_games.lock().await;
+        games.values_mut().next().unwrap().is_active = false;
+    }
+
+    send(&state, first, ClientCommand::Roll).await;
+    send(&state, first, ClientCommand::RejectTrade(Uuid::new_v4())).await;
+
+    let rx = &mut players[0].1;
+    for _ in 0..2 {
+        let message = rx.try_recv().unwrap();
+        assert!(message.contains("The game is over"));
+    }
+}
+
+#[tokio::test]
+async fn buying_needs_an_offer() {
+    let (state, mut players) = game_with_two_players();
+    let (first, rx) = &mut players[0];
+    {
+        let mut games = state.active_games.lock().await;
+        let game = games.values_mut().next().unwrap();
+        game.players[0].position = 1;
+    }
+
+    send(&state, *first, ClientCommand::BuyProperty).await;
+    send(&state, *first, ClientCommand::SkipBuyProperty).await;
+
+    assert_eq!(next_error(rx), ErrorCode::IllegalMove);
+    assert_eq!(next_error(rx), ErrorCode::IllegalMove);
+    let games = state.active_games.lock().await;
+    let game = games.values().next().unwrap();
+    assert_eq!(game.board[1].owner(), None);
+    assert!(game.auction.is_none());
+    assert_eq!(game.phase, TurnPhase::AwaitingRoll);
+}
+
+#[tokio::test]
+async fn roll_waits_for_the_buy_decision() {
+    let (state, mut players) = game_with_two_players();
+    let second = players[1].0;
+    let (first, rx) = &mut players[0];
+    {
+        let mut games = state.active_games.lock().await;
+        let game = games.values_mut().next().unwrap();
+        // Lands on an unowned property
+        game.dice = DiceModel::Loaded(VecDeque::from([DiceRoll {
+            dice1: 1,
+            dice2: 2,
+            speed_die: None,
+        }]));
+    }
+
+    send(&state, *first, ClientCommand::Roll).await;
+    assert_eq!(
+        state
+            .active_games
+            .lock()
+            .await
+            .values()
+            .next()
+            .unwrap()
+            .phase,
+        TurnPhase::AwaitingBuyDecision
+    );
+    send(&state, *first, ClientCommand::Roll).await;
+    assert_eq!(next_error(rx), ErrorCode::IllegalMove);
+    send(&state, *first, ClientCommand::BuyProperty).await;
+
+    let games = state.active_games.lock().await;
+    let game = games.values().next().unwrap();
+    assert!(matches!(game.board[3], Tile::Property { owner: Some(owner), .. } if owner == *first));
+    assert_eq!(game.players[game.player_turn].id, second);
+    assert_eq!(game.phase, TurnPhase::AwaitingRoll);
+}
+
+#[tokio::test]
+async fn jail_fine_is_paid_before_rolling() {
+    let (state, mut players) = game_with_two_players();
+    let (first, rx) = &mut players[0];
+
+    send(&state, *first, ClientCommand::PayJailFine).await;
+    assert_eq!(next_error(rx), ErrorCode::IllegalMove);
+
+    {
+        let mut games = state.active_games.lock().await;
+        let game = games.values_mut().next().unwrap();
+        game.players[0].is_in_jail = true;
+        game.phase = TurnPhase::InJailDecision;
+    }
+    send(&state, *first, ClientCommand::PayJailFine).await;
+
+    let games = state.active_games.lock().await;
+    let game = games.values().next().unwrap();
+    assert!(!game.players[0].is_in_jail);
+    assert_eq!(game.phase, TurnPhase::AwaitingRoll);
+}
+
+#[cfg(not(feature = "debug-commands"))]
+#[tokio::test]
+async fn debug_commands_are_refused() {
+    let (state, mut players) = game_with_two_players();
+    let (first, rx) = &mut players[0];
+    let message = r#"{"command":"Debug","data":{"command":"BuyAll"}}"#;
+
+    handle_message_in_game(message, &state, *first).await;
+
+    assert_eq!(next_error(rx), ErrorCode::IllegalMove);
+    assert_eq!(handle_message(message), Err(CommandError::DebugDisabled));
+    let games = state.active_games.lock().await;
+    let game = games.values().next().unwrap();
+    assert!(game.board.iter().all(|tile| tile.owner().is_none()));
+}
+
+#[test]
+fn waiting_room_commands_are_refused() {
+    let message = serde_json::to_string(&ClientCommand::Roll).unwrap();
+    assert_eq!(handle_message(&message), Err(CommandError::NotStarted));
+    assert!(matches!(
+        handle_message("garbage"),
+        Err(CommandError::Malformed(_))
+    ));
+}
+
+#[tokio::test]
+async fn leaving_bidder_drops_out_of_the_auction() {
+    let (state, players) = game_with_two_players();
+    let (first, second) = (players[0].0, players[1].0);
+    {
+        let mut games = state.active_games.lock().await;
+        let game = games.values_mut().next().unwrap();
+        game.players[0].position = 1;
+        game.phase = TurnPhase::AwaitingBuyDecision;
+    }
+    send(&state, first, ClientCommand::SkipBuyProperty).await;
+    send(&state, second, ClientCommand::PlaceBid(10)).await;
+
+    remove_player(&state, second).await;
+
+    let games = state.active_games.lock().await;
+    let auction = games.values().next().unwrap().auction.as_ref().unwrap();
+    assert_eq!(auction.bidders, vec![first]);
+    assert_eq!(auction.highest_bidder, None);
+}
+
+#[tokio::test]
+async fn leaving_debtor_releases_the_turn() {
+    let (state, players) = game_with_two_players();
+    let (first, second) = (players[0].0, players[1].0);
+    {
+        let mut games = state.active_games.lock().await;
+        let game = games.values_mut().next().unwrap();
+        let (tx, _) = mpsc::channel(32);
+        game.players.push(test_player(Uuid::new_v4(), tx));
+        game.players[1].money = 10;
+        if let Tile::Railroad { owner, .. } = &mut game.board[5] {
+            *owner = Some(second);
+        }
+        assert_eq!(charge(game, second, Some(first), 50).await, Payment::Debt);
+    }
+
+    remove_player(&state, second).await;
+
+    let games = state.active_games.lock().await;
+    let game = games.values().next().unwrap();
+    assert!(game.debts.is_empty());
+    assert_eq!(game.phase, TurnPhase::AwaitingRoll);
+    assert_eq!(game.player_turn, 1);
+}
+
+#[tokio::test]
+async fn leaving_creditor_drops_what_they_were_owed() {
+    let (state, players) = game_with_two_players();
+    let (first, second) = (players[0].0, players[1].0);
+    {
+        let mut games = state.active_games.lock().await;
+        let game = games.values_mut().next().unwrap();
+        let (tx, _) = mpsc::channel(32);
+        game.players.push(test_player(Uuid::new_v4(), tx));
+        game.players[1].money = 10;
+        game.board[5].set_owner(Some(second));
+        assert_eq!(charge(game, second, Some(first), 50).await, Payment::Debt);
+    }
+
+    // The turn player leaves while still owed
+    remove_player(&state, first).await;
+
+    let games = state.active_games.lock().await;
+    let game = games.values().next().unwrap();
+    assert!(game.debts.is_empty());
+    assert_eq!(game.phase, TurnPhase::AwaitingRoll);
+    assert_eq!(game.players[game.player_turn].id, second);
+}
+
+#[tokio::test]
+async fn any_player_can_ask_for_a_house_during_a_shortage() {
+    let (state, players) = game_with_two_players();
+    let (first, second) = (players[0].0, players[1].0);
+    {
+        let mut games = state.active_games.lock().await;
+        let game = games.values_mut().next().unwrap();
+        for (position, owner) in [(1, first), (3, first), (37, second), (39, second)] {
+            game.board[position].set_owner(Some(owner));
+        }
+        game.houses_available = 1;
+    }
+
+    // Out of turn, the request is refused until a shortage window is open
+    send(&state, second, ClientCommand::BuildHouse(37)).await;
+    assert!(state
+        .active_games
+        .lock()
+        .await
+        .values()
+        .next()
+        .unwrap()
+        .house_requests
+        .is_empty());
+    send(&state, first, ClientCommand::BuildHouse(1)).await;
+    send(&state, second, ClientCommand::BuildHouse(37)).await;
+
+    let games = state.active_games.lock().await;
+    assert_eq!(games.values().next().unwrap().house_requests.len(), 2);
+}
@@ -0,0 +1,152 @@
+use crate::debt::{charge, declare_bankruptcy, settle_debts, DebtError, Payment};
+use crate::game_state::{Game, Player};
+use crate::mortgage::mortgage;
+use crate::test::test_player;
+use shared::board::{PropertyLevel, Tile};
+use shared::cards::{CardDeck, CardEffect};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+// The first player has 10 money and owns the first railroad
+fn game_with_debtor() -> (Game, Uuid, Uuid) {
+    let mut game = Game::default();
+    let debtor = Uuid::new_v4();
+    let creditor = Uuid::new_v4();
+    let (tx, _) = mpsc::channel(32);
+    game.players = vec![
+        Player {
+            money: 10,
+            ..test_player(debtor, tx.clone())
+        },
+        test_player(creditor, tx),
+    ];
+    if let Tile::Railroad { owner, .. } = &mut game.board[5] {
+        *owner = Some(debtor);
+    }
+    (game, debtor, creditor)
+}
+
+#[tokio::test]
+async fn debt_blocks_turn_until_paid() {
+    let (mut game, debtor, creditor) = game_with_debtor();
+
+    assert_eq!(
+        charge(&mut game, debtor, Some(creditor), 50).await,
+        Payment::Debt
+    );
+    assert_eq!(game.debts.len(), 1);
+    assert!(!game.players[0].is_bankrupt);
+
+    // Nothing happens while the debtor can't pay
+    settle_debts(&mut game).await;
+    assert_eq!(game.debts.len(), 1);
+    assert_eq!(game.player_turn, 0);
+
+    assert!(mortgage(debtor, &mut game, 5).await.is_ok());
+    settle_debts(&mut game).await;
+    assert!(game.debts.is_empty());
+    assert_eq!(game.players[0].money, 60);
+    assert_eq!(game.players[1].money, 1550);
+    assert_eq!(game.player_turn, 1);
+}
+
+#[tokio::test]
+async fn bankrupt_when_assets_are_short() {
+    let (mut game, debtor, creditor) = game_with_debtor();
+
+    assert_eq!(
+        charge(&mut game, debtor, Some(creditor), 500).await,
+        Payment::Bankrupt
+    );
+    assert!(game.debts.is_empty());
+    assert!(game.players[0].is_bankrupt);
+    // Cash and tiles go to the creditor
+    assert_eq!(game.players[0].money, 0);
+    assert_eq!(game.players[1].money, 1510);
+    assert_eq!(game.board[5].owner(), Some(creditor));
+}
+
+#[tokio::test]
+async fn bankrupt_to_bank_frees_tiles() {
+    let (mut game, debtor, _) = game_with_debtor();
+    if let Tile::Property { owner, level, .. } = &mut game.board[1] {
+        *owner = Some(debtor);
+        *level = PropertyLevel::House2;
+    }
+    game.board[5].set_mortgaged(true);
+    let houses = game.houses_available;
+
+    assert_eq!(
+        charge(&mut game, debtor, None, 500).await,
+        Payment::Bankrupt
+    );
+    assert_eq!(game.board[1].owner(), None);
+    assert_eq!(game.board[5].owner(), None);
+    assert!(!game.board[5].is_mortgaged());
+    assert!(matches!(
+        game.board[1],
+        Tile::Property {
+            level: PropertyLevel::None,
+            ..
+        }
+    ));
+    assert_eq!(game.houses_available, houses + 2);
+}
+
+#[tokio::test]
+async fn declare_bankruptcy_clears_debt() {
+    let (mut game, debtor, _) = game_with_debtor();
+
+    assert_eq!(
+        declare_bankruptcy(&mut game, debtor).await,
+        Err(DebtError::NoDebt)
+    );
+    charge(&mut game, debtor, None, 50).await;
+    assert!(declare_bankruptcy(&mut game, debtor).await.is_ok());
+    assert!(game.debts.is_empty());
+    assert!(game.players[0].is_bankrupt);
+    assert_eq!(game.players[1].money, 1500);
+    // Only the creditor is left, the game is over
+    assert!(!game.is_active);
+}
+
+#[tokio::test]
+async fn debts_of_a_player_who_left_are_dropped() {
+    let (mut game, debtor, creditor) = game_with_debtor();
+    assert_eq!(
+        charge(&mut game, debtor, Some(creditor), 50).await,
+        Payment::Debt
+    );
+    game.players.remove(0);
+    game.player_turn = 0;
+
+    settle_debts(&mut game).await;
+
+    assert!(game.debts.is_empty());
+}
+
+#[tokio::test]
+async fn bankruptcy_hands_over_jail_cards() {
+    let (mut game, debtor, creditor) = game_with_debtor();
+    game.players[0].jail_cards = vec![CardDeck::Chance];
+
+    charge(&mut game, debtor, Some(creditor), 500).await;
+
+    assert!(game.players[0].jail_cards.is_empty());
+    assert_eq!(game.players[1].jail_cards, vec![CardDeck::Chance]);
+}
+
+#[tokio::test]
+async fn bankruptcy_to_bank_returns_jail_cards() {
+    let (mut game, debtor, _) = game_with_debtor();
+    game.players[0].jail_cards = vec![CardDeck::CommunityChest];
+    game.community_chest_cards
+        .cards
+        .retain(|card| card.effect != CardEffect::GetOutOfJailFree);
+
+    charge(&mut game, debtor, None, 500).await;
+
+    assert!(game.players[0].jail_cards.is_empty());
+    let returned = game.community_chest_cards.cards.last().unwrap();
+    assert_eq!(returned.effect, CardEffect::GetOutOfJailFree);
+}
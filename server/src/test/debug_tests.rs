@@ -0,0 +1,120 @@
+use crate::action::roll_dice;
+use crate::debug::{run_debug_command, DebugError};
+use crate::game_state::{Game, Player};
+use shared::action::{DebugCommand, ForcedRollData, ServerEvent};
+use shared::board::Tile;
+use shared::cards::{CardDeck, CardEffect};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+fn test_game() -> (Game, Uuid) {
+    let mut game = Game::default();
+    let (tx, _) = mpsc::channel(32);
+    let player = Player::default(tx, "Developer".to_owned());
+    let player_id = player.id;
+    game.players = vec![player];
+    (game, player_id)
+}
+
+#[tokio::test]
+async fn buy_all_and_set_money() {
+    let (mut game, player_id) = test_game();
+    let (tx, mut rx) = mpsc::channel(32);
+    game.players[0].tx = tx;
+
+    run_debug_command(&mut game, player_id, DebugCommand::BuyAll)
+        .await
+        .unwrap();
+    run_debug_command(&mut game, player_id, DebugCommand::SetMoney(42))
+        .await
+        .unwrap();
+
+    assert!(game
+        .board
+        .iter()
+        .filter(|tile| matches!(tile, Tile::Property { .. }))
+        .all(|tile| tile.owner() == Some(player_id)));
+    assert_eq!(game.players[0].money, 42);
+
+    // The clients get the new owners and money
+    let mut refreshes = vec![];
+    while let Ok(message) = rx.try_recv() {
+        if let ServerEvent::StateRefresh(data) = serde_json::from_str(&message).unwrap() {
+            refreshes.push(data);
+        }
+    }
+    assert_eq!(refreshes.len(), 2);
+    assert_eq!(refreshes[0].board[1].owner(), Some(player_id));
+    assert_eq!(refreshes[1].players[0].money, 42);
+}
+
+#[tokio::test]
+async fn forced_dice_move_the_player() {
+    let (mut game, player_id) = test_game();
+    let forced = ForcedRollData {
+        dice1: 1,
+        dice2: 2,
+        speed_die: None,
+    };
+
+    run_debug_command(&mut game, player_id, DebugCommand::ForceDice(forced))
+        .await
+        .unwrap();
+    roll_dice(&mut game, &player_id).await;
+
+    assert_eq!(game.players[0].position, 3);
+    let invalid = ForcedRollData {
+        dice1: 7,
+        dice2: 1,
+        speed_die: None,
+    };
+    assert_eq!(
+        run_debug_command(&mut game, player_id, DebugCommand::ForceDice(invalid)).await,
+        Err(DebugError::InvalidDice)
+    );
+}
+
+#[tokio::test]
+async fn teleport_stays_on_the_board() {
+    let (mut game, player_id) = test_game();
+
+    run_debug_command(&mut game, player_id, DebugCommand::Teleport(10))
+        .await
+        .unwrap();
+
+    assert_eq!(game.players[0].position, 10);
+    let outside = game.board.len();
+    assert_eq!(
+        run_debug_command(&mut game, player_id, DebugCommand::Teleport(outside)).await,
+        Err(DebugError::InvalidTile)
+    );
+}
+
+#[tokio::test]
+async fn jail_card_leaves_the_deck() {
+    let (mut game, player_id) = test_game();
+
+    run_debug_command(
+        &mut game,
+        player_id,
+        DebugCommand::GiveCard(CardDeck::Chance),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(game.players[0].jail_cards, vec![CardDeck::Chance]);
+    assert!(!game
+        .chance_cards
+        .cards
+        .iter()
+        .any(|card| card.effect == CardEffect::GetOutOfJailFree));
+    assert_eq!(
+        run_debug_command(
+            &mut game,
+            player_id,
+            DebugCommand::GiveCard(CardDeck::Chance)
+        )
+        .await,
+        Err(DebugError::NoCardLeft)
+    );
+}
@@ -0,0 +1,113 @@
+use crate::action::{buy_property, roll_dice};
+use crate::dice::{
+    mr_monopoly_target, take_bus, teleport, DiceError, DiceModel, DiceRoll, PendingMove,
+};
+use crate::game_state::Game;
+use crate::test::test_player;
+use shared::action::SpeedDieFace;
+use std::collections::VecDeque;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+fn speed_game(rolls: Vec<(u8, u8, SpeedDieFace)>) -> (Game, Uuid) {
+    let mut game = Game::default();
+    let player_id = Uuid::new_v4();
+    let (tx, _) = mpsc::channel(32);
+    game.players = vec![test_player(player_id, tx)];
+    game.rules.speed_die = true;
+    game.dice = DiceModel::Loaded(
+        rolls
+            .into_iter()
+            .map(|(dice1, dice2, face)| DiceRoll {
+                dice1,
+                dice2,
+                speed_die: Some(face),
+            })
+            .collect::<VecDeque<_>>(),
+    );
+    (game, player_id)
+}
+
+#[tokio::test]
+async fn speed_die_number_adds_to_the_roll() {
+    let (mut game, player_id) = speed_game(vec![(2, 3, SpeedDieFace::Number(1))]);
+
+    roll_dice(&mut game, &player_id).await;
+    assert_eq!(game.players[0].position, 6);
+    assert_eq!(game.pending_move, None);
+}
+
+#[tokio::test]
+async fn triple_teleports_anywhere() {
+    let (mut game, player_id) = speed_game(vec![(1, 1, SpeedDieFace::Number(1))]);
+
+    roll_dice(&mut game, &player_id).await;
+    assert_eq!(game.players[0].position, 0);
+    assert_eq!(game.pending_move, Some(PendingMove::Teleport));
+
+    assert_eq!(
+        teleport(&mut game, player_id, 40).await,
+        Err(DiceError::InvalidTile)
+    );
+    assert!(teleport(&mut game, player_id, 39).await.is_ok());
+    assert_eq!(game.players[0].position, 39);
+    assert_eq!(
+        teleport(&mut game, player_id, 1).await,
+        Err(DiceError::NoTeleport)
+    );
+}
+
+#[tokio::test]
+async fn bus_moves_by_the_chosen_die() {
+    let (mut game, player_id) = speed_game(vec![(2, 5, SpeedDieFace::Bus)]);
+
+    roll_dice(&mut game, &player_id).await;
+    assert_eq!(game.players[0].position, 0);
+    assert_eq!(
+        take_bus(&mut game, player_id, 4).await,
+        Err(DiceError::InvalidSteps)
+    );
+    assert!(take_bus(&mut game, player_id, 5).await.is_ok());
+    assert_eq!(game.players[0].position, 5);
+    assert_eq!(game.pending_move, None);
+}
+
+#[tokio::test]
+async fn mr_monopoly_moves_after_the_tile_is_resolved() {
+    let (mut game, player_id) = speed_game(vec![(1, 2, SpeedDieFace::MrMonopoly)]);
+
+    roll_dice(&mut game, &player_id).await;
+    assert_eq!(game.players[0].position, 3);
+    assert!(game.mr_monopoly);
+
+    // Buying the tile closes it, Mr. Monopoly then heads to the next unowned tile
+    assert!(buy_property(player_id, &mut game).await.is_ok());
+    assert_eq!(game.players[0].position, 5);
+    assert!(!game.mr_monopoly);
+}
+
+#[tokio::test]
+async fn mr_monopoly_targets_rent_once_everything_is_owned() {
+    let (mut game, player_id) = speed_game(vec![]);
+    let other = Uuid::new_v4();
+    for tile in game.board.iter_mut() {
+        tile.set_owner(Some(other));
+    }
+    game.board[1].set_owner(Some(player_id));
+    game.board[3].set_mortgaged(true);
+
+    assert_eq!(mr_monopoly_target(&game, player_id), Some(5));
+}
+
+#[tokio::test]
+async fn triple_is_not_counted_as_doubles() {
+    let (mut game, player_id) = speed_game(vec![(2, 2, SpeedDieFace::Number(2))]);
+    game.players[0].doubles_count = 2;
+
+    roll_dice(&mut game, &player_id).await;
+
+    // No third double: the player picks a tile and the turn won't repeat
+    assert!(!game.players[0].is_in_jail);
+    assert_eq!(game.players[0].doubles_count, 0);
+    assert_eq!(game.pending_move, Some(PendingMove::Teleport));
+}
@@ -1,7 +1,12 @@
-use crate::game_state::{start_new_game, Game, Player, WaitingRoom};
+use crate::game_state::{run_time_limit, start_new_game, Game, Player, TurnPhase, WaitingRoom};
 use crate::server_state::ServerState;
+use crate::test::test_player;
+use shared::action::{GameOverReason, ServerEvent};
+use shared::maps::map1::MAP1;
+use shared::rules::GameRules;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::{mpsc, Mutex};
 use uuid::Uuid;
 
@@ -13,26 +18,8 @@ async fn advance_turn() {
     let (tx2, _) = mpsc::channel(32);
 
     game.players = vec![
-        Player {
-            id: Uuid::new_v4(),
-            name: "Player1".to_owned(),
-            tx: tx1,
-            money: 1500,
-            position: 0,
-            is_in_jail: false,
-            jail_turns: 0,
-            is_bankrupt: false,
-        },
-        Player {
-            id: Uuid::new_v4(),
-            name: "Player2".to_owned(),
-            tx: tx2,
-            money: 1500,
-            position: 0,
-            is_in_jail: false,
-            jail_turns: 0,
-            is_bankrupt: false,
-        },
+        test_player(Uuid::new_v4(), tx1),
+        test_player(Uuid::new_v4(), tx2),
     ];
 
     // Initial player turn
@@ -58,36 +45,13 @@ async fn advance_turn_with_bankrupt_player() {
     let (tx3, _) = mpsc::channel(32);
 
     game.players = vec![
+        test_player(Uuid::new_v4(), tx1),
         Player {
-            id: Uuid::new_v4(),
-            name: "Player1".to_owned(),
-            tx: tx1,
-            money: 1500,
-            position: 0,
-            is_in_jail: false,
-            jail_turns: 0,
-            is_bankrupt: false,
-        },
-        Player {
-            id: Uuid::new_v4(),
-            name: "BankruptPlayer".to_owned(),
-            tx: tx2,
             money: 0,
-            position: 0,
-            is_in_jail: false,
-            jail_turns: 0,
             is_bankrupt: true, // This player is bankrupt
+            ..test_player(Uuid::new_v4(), tx2)
         },
-        Player {
-            id: Uuid::new_v4(),
-            name: "Player3".to_owned(),
-            tx: tx3,
-            money: 1500,
-            position: 0,
-            is_in_jail: false,
-            jail_turns: 0,
-            is_bankrupt: false,
-        },
+        test_player(Uuid::new_v4(), tx3),
     ];
 
     // Current player turn
@@ -108,32 +72,16 @@ async fn start_new_game_works() {
 
     let waiting_room = WaitingRoom {
         players: vec![
-            Player {
-                id: Uuid::new_v4(),
-                name: "WaitingPlayer1".to_owned(),
-                tx: tx1,
-                money: 1500,
-                position: 0,
-                is_in_jail: false,
-                jail_turns: 0,
-                is_bankrupt: false,
-            },
-            Player {
-                id: Uuid::new_v4(),
-                name: "WaitingPlayer2".to_owned(),
-                tx: tx2,
-                money: 1500,
-                position: 0,
-                is_in_jail: false,
-                jail_turns: 0,
-                is_bankrupt: false,
-            },
+            test_player(Uuid::new_v4(), tx1),
+            test_player(Uuid::new_v4(), tx2),
         ],
     };
 
     let state = Arc::new(ServerState {
         waiting_room: Mutex::new(waiting_room),
         active_games: Mutex::new(HashMap::new()),
+        rules: GameRules::default(),
+        board: MAP1.clone(),
     });
 
     // Start a new game
@@ -151,4 +99,129 @@ async fn start_new_game_works() {
 
     assert!(msg1.contains("GameStart"));
     assert!(msg2.contains("GameStart"));
+
+    // The clients get the board and the starting state of every player
+    let ServerEvent::GameStart(data) = serde_json::from_str(&msg1).unwrap() else {
+        panic!("Expected the game start");
+    };
+    assert_eq!(data.board.len(), MAP1.len());
+    assert_eq!(data.board[39].price(), Some(400));
+    assert_eq!(data.players.len(), 2);
+    assert!(data
+        .players
+        .iter()
+        .all(|player| player.money == 1500 && player.position == 0));
+}
+
+#[tokio::test]
+async fn doubles_keep_the_turn() {
+    let mut game = Game::default();
+    let (tx, _) = mpsc::channel(32);
+    game.players = (0..2)
+        .map(|_| test_player(Uuid::new_v4(), tx.clone()))
+        .collect();
+
+    game.players[0].doubles_count = 1;
+    game.end_turn().await;
+    assert_eq!(game.player_turn, 0);
+
+    // Passing the turn resets the doubles
+    game.advance_turn().await;
+    assert_eq!(game.player_turn, 1);
+    assert_eq!(game.players[0].doubles_count, 0);
+    game.end_turn().await;
+    assert_eq!(game.player_turn, 0);
+}
+
+fn two_players(game: &mut Game) -> mpsc::Receiver<String> {
+    let (tx1, rx1) = mpsc::channel(32);
+    let (tx2, _) = mpsc::channel(32);
+    game.players = vec![
+        Player {
+            money: 1000,
+            ..test_player(Uuid::new_v4(), tx1)
+        },
+        Player {
+            money: 900,
+            ..test_player(Uuid::new_v4(), tx2)
+        },
+    ];
+    rx1
+}
+
+#[tokio::test]
+async fn turn_limit_ends_on_net_worth() {
+    let mut game = Game::default();
+    let mut rx = two_players(&mut game);
+    game.rules.max_turns = Some(2);
+    // The boardwalk makes the poorer player the richest one
+    let second = game.players[1].id;
+    game.board[39].set_owner(Some(second));
+
+    game.advance_turn().await;
+    assert!(game.is_active);
+    game.advance_turn().await;
+    assert!(!game.is_active);
+
+    let mut data = None;
+    while let Ok(message) = rx.try_recv() {
+        if let ServerEvent::GameOver(game_over) = serde_json::from_str(&message).unwrap() {
+            data = Some(game_over);
+        }
+    }
+    let data = data.unwrap();
+    assert_eq!(data.winner, second);
+    assert_eq!(data.reason, GameOverReason::TurnLimit);
+    assert_eq!(data.standings[0].net_worth, 900 + 400);
+    assert_eq!(data.standings[1].net_worth, 1000);
+}
+
+#[tokio::test]
+async fn time_limit_ends_at_the_turn_change() {
+    let mut game = Game::default();
+    two_players(&mut game);
+    game.rules.time_limit_minutes = Some(1);
+
+    game.advance_turn().await;
+    assert!(game.is_active);
+    game.started_at -= Duration::from_secs(60);
+    game.advance_turn().await;
+    assert!(!game.is_active);
+    assert_eq!(game.standings()[0].player, game.players[0].id);
+}
+
+#[tokio::test]
+async fn time_limit_ends_the_game_mid_turn() {
+    let mut game = Game::default();
+    let mut rx = two_players(&mut game);
+    game.phase = TurnPhase::AwaitingBuyDecision;
+    let game_id = game.id;
+    let state = Arc::new(ServerState {
+        waiting_room: Mutex::new(WaitingRoom { players: vec![] }),
+        active_games: Mutex::new(HashMap::from([(game_id, game)])),
+        rules: GameRules::default(),
+        board: MAP1.clone(),
+    });
+
+    run_time_limit(Arc::clone(&state), game_id, Duration::from_millis(10)).await;
+
+    let games = state.active_games.lock().await;
+    assert!(!games[&game_id].is_active);
+    let message = rx.try_recv().unwrap();
+    let ServerEvent::GameOver(data) = serde_json::from_str(&message).unwrap() else {
+        panic!("Expected the game to be over");
+    };
+    assert_eq!(data.reason, GameOverReason::TimeLimit);
+}
+
+#[tokio::test]
+async fn jailed_player_starts_their_turn_in_jail() {
+    let mut game = Game::default();
+    two_players(&mut game);
+    game.players[1].is_in_jail = true;
+
+    game.advance_turn().await;
+    assert_eq!(game.phase, TurnPhase::InJailDecision);
+    game.advance_turn().await;
+    assert_eq!(game.phase, TurnPhase::AwaitingRoll);
 }
@@ -0,0 +1,51 @@
+use crate::handshake::{check_handshake, HandshakeError};
+use shared::action::{ClientCommand, IdentifyData};
+use shared::list_const::PROTOCOL_VERSION;
+
+fn identify(name: &str, protocol_version: u32) -> String {
+    serde_json::to_string(&ClientCommand::Identify(IdentifyData {
+        name: name.to_owned(),
+        protocol_version,
+        capabilities: vec!["trade".to_owned()],
+    }))
+    .unwrap()
+}
+
+#[test]
+fn matching_version_is_welcomed() {
+    let data = check_handshake(&identify("Alice", PROTOCOL_VERSION)).unwrap();
+    assert_eq!(data.name, "Alice");
+    assert_eq!(data.capabilities, vec!["trade".to_owned()]);
+}
+
+#[test]
+fn other_version_is_rejected() {
+    let result = check_handshake(&identify("Alice", PROTOCOL_VERSION + 1));
+    assert_eq!(
+        result.unwrap_err(),
+        HandshakeError::VersionMismatch(PROTOCOL_VERSION + 1)
+    );
+}
+
+#[test]
+fn old_or_malformed_first_message_is_rejected() {
+    // Clients before the handshake sent the name alone
+    let old = r#"{"command":"Identify","data":"Alice"}"#;
+    assert_eq!(
+        check_handshake(old).unwrap_err(),
+        HandshakeError::NotIdentify
+    );
+    assert_eq!(
+        check_handshake("hello").unwrap_err(),
+        HandshakeError::NotIdentify
+    );
+    let roll = serde_json::to_string(&ClientCommand::Roll).unwrap();
+    assert_eq!(
+        check_handshake(&roll).unwrap_err(),
+        HandshakeError::NotIdentify
+    );
+    assert_eq!(
+        check_handshake(&identify(" ", PROTOCOL_VERSION)).unwrap_err(),
+        HandshakeError::EmptyName
+    );
+}
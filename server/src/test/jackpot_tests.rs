@@ -0,0 +1,48 @@
+use crate::action::land_on_tile;
+use crate::game_state::{Game, Player};
+use crate::jail::pay_jail_fine;
+use crate::test::test_player;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+fn test_game(jackpot: bool) -> (Game, Uuid) {
+    let mut game = Game::default();
+    let player_id = Uuid::new_v4();
+    let (tx, _) = mpsc::channel(32);
+    game.players = vec![
+        Player {
+            position: 4,
+            ..test_player(player_id, tx.clone())
+        },
+        test_player(Uuid::new_v4(), tx),
+    ];
+    game.rules.free_parking_jackpot = jackpot;
+    (game, player_id)
+}
+
+#[tokio::test]
+async fn taxes_and_fines_fill_the_pot() {
+    let (mut game, player_id) = test_game(true);
+
+    land_on_tile(&mut game, &player_id, 4).await;
+    assert_eq!(game.free_parking_pot, 200);
+
+    game.players[0].is_in_jail = true;
+    assert!(pay_jail_fine(&mut game, player_id).await.is_ok());
+    assert_eq!(game.free_parking_pot, 250);
+
+    game.player_turn = 0;
+    game.players[0].position = 20;
+    land_on_tile(&mut game, &player_id, 4).await;
+    assert_eq!(game.free_parking_pot, 0);
+    assert_eq!(game.players[0].money, 1500);
+}
+
+#[tokio::test]
+async fn no_pot_without_the_rule() {
+    let (mut game, player_id) = test_game(false);
+
+    land_on_tile(&mut game, &player_id, 4).await;
+    assert_eq!(game.free_parking_pot, 0);
+    assert_eq!(game.players[0].money, 1300);
+}
@@ -0,0 +1,102 @@
+use crate::action::roll_dice;
+use crate::debt::settle_debts;
+use crate::dice::{DiceModel, DiceRoll};
+use crate::game_state::{Game, Player, TurnPhase};
+use crate::jail::{pay_jail_fine, use_jail_card, JailError};
+use crate::mortgage::mortgage;
+use crate::test::test_player;
+use shared::board::Tile;
+use shared::cards::{CardDeck, CardEffect};
+use std::collections::VecDeque;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+fn jailed_game(jail_turns: u8) -> (Game, Uuid) {
+    let mut game = Game::default();
+    let player_id = Uuid::new_v4();
+    let (tx, _) = mpsc::channel(32);
+    game.players = vec![
+        Player {
+            position: 10,
+            is_in_jail: true,
+            jail_turns,
+            ..test_player(player_id, tx.clone())
+        },
+        test_player(Uuid::new_v4(), tx),
+    ];
+    (game, player_id)
+}
+
+#[tokio::test]
+async fn pay_fine_before_rolling() {
+    let (mut game, player_id) = jailed_game(3);
+
+    assert!(pay_jail_fine(&mut game, player_id).await.is_ok());
+    assert!(!game.players[0].is_in_jail);
+    assert_eq!(game.players[0].money, 1450);
+    assert_eq!(
+        pay_jail_fine(&mut game, player_id).await,
+        Err(JailError::NotInJail)
+    );
+}
+
+#[tokio::test]
+async fn jail_card_goes_back_to_its_deck() {
+    let (mut game, player_id) = jailed_game(3);
+    assert_eq!(
+        use_jail_card(&mut game, player_id).await,
+        Err(JailError::NoJailCard)
+    );
+
+    game.chance_cards
+        .cards
+        .retain(|card| card.effect != CardEffect::GetOutOfJailFree);
+    game.players[0].jail_cards.push(CardDeck::Chance);
+    assert!(use_jail_card(&mut game, player_id).await.is_ok());
+    assert!(!game.players[0].is_in_jail);
+    assert!(game.players[0].jail_cards.is_empty());
+    assert_eq!(
+        game.chance_cards.cards.last().unwrap().effect,
+        CardEffect::GetOutOfJailFree
+    );
+}
+
+#[tokio::test]
+async fn third_failed_roll_forces_the_fine() {
+    let (mut game, player_id) = jailed_game(1);
+    // Keep the landing tiles free of side effects
+    for tile in &mut game.board[12..=22] {
+        *tile = Tile::FreeParking;
+    }
+
+    let (roll1, roll2) = roll_dice(&mut game, &player_id).await;
+
+    // The player leaves jail either way and moves with the roll
+    assert!(!game.players[0].is_in_jail);
+    assert_eq!(game.players[0].position, 10 + (roll1 + roll2) as usize);
+    let fine = if roll1 == roll2 { 0 } else { 50 };
+    assert_eq!(game.players[0].money, 1500 - fine);
+}
+
+#[tokio::test]
+async fn third_failed_roll_moves_once_the_fine_is_raised() {
+    let (mut game, player_id) = jailed_game(1);
+    game.players[0].money = 10;
+    game.board[5].set_owner(Some(player_id));
+    game.board[13] = Tile::FreeParking;
+    game.dice = DiceModel::Loaded(VecDeque::from([DiceRoll {
+        dice1: 1,
+        dice2: 2,
+        speed_die: None,
+    }]));
+
+    roll_dice(&mut game, &player_id).await;
+    assert_eq!(game.phase, TurnPhase::PayingDebt);
+    assert_eq!(game.players[0].position, 10);
+
+    assert!(mortgage(player_id, &mut game, 5).await.is_ok());
+    settle_debts(&mut game).await;
+    assert!(game.debts.is_empty());
+    assert_eq!(game.players[0].position, 13);
+    assert_eq!(game.players[0].money, 60);
+}
@@ -0,0 +1,80 @@
+use shared::board::Tile;
+use shared::maps::loader::{load_map, MapError};
+use shared::maps::map1::MAP1;
+
+#[test]
+fn classic_map_round_trips() {
+    let content = serde_json::to_string(&*MAP1).unwrap();
+    let board = load_map(&content).unwrap();
+    assert_eq!(board.len(), MAP1.len());
+    assert_eq!(board[39].price(), Some(400));
+}
+
+#[test]
+fn map_files_leave_out_the_game_state() {
+    let content = r#"[
+        {"Go": {"amount": 200}},
+        {"Property": {"name": "Old Town", "costs": [60, 50], "rents": [2, 10, 30, 90, 160, 250], "group": "Brown"}},
+        {"Railroad": {"cost": 200, "rents": [25, 50, 100, 200]}},
+        {"Utility": {"cost": 150}},
+        "Jail",
+        "GoToJail"
+    ]"#;
+    let board = load_map(content).unwrap();
+    assert_eq!(board.len(), 6);
+    assert_eq!(board[1].owner(), None);
+    assert!(!board[2].is_mortgaged());
+}
+
+#[test]
+fn invalid_maps_are_rejected() {
+    assert!(matches!(
+        load_map("[{\"Go\": 200}]"),
+        Err(MapError::Parse(_))
+    ));
+    assert_eq!(
+        load_map(r#"["Jail", {"Go": {"amount": 200}}]"#).unwrap_err(),
+        MapError::NoGoAtStart
+    );
+    assert_eq!(
+        load_map(r#"[{"Go": {"amount": 200}}, "GoToJail"]"#).unwrap_err(),
+        MapError::JailCount(0)
+    );
+    assert_eq!(
+        load_map(r#"[{"Go": {"amount": 200}}, "FreeParking"]"#).unwrap_err(),
+        MapError::JailCount(0)
+    );
+    assert_eq!(
+        load_map(r#"[{"Go": {"amount": 200}}, "Jail", "Jail", "GoToJail"]"#).unwrap_err(),
+        MapError::JailCount(2)
+    );
+
+    let mut board = MAP1.clone();
+    if let Tile::Property { rents, .. } = &mut board[1] {
+        rents.pop();
+    }
+    let content = serde_json::to_string(&board).unwrap();
+    assert_eq!(load_map(&content).unwrap_err(), MapError::PropertyRents(1));
+
+    let mut board = MAP1.clone();
+    if let Tile::Railroad { rents, .. } = &mut board[5] {
+        rents.push(400);
+    }
+    let content = serde_json::to_string(&board).unwrap();
+    assert_eq!(load_map(&content).unwrap_err(), MapError::RailroadRents(5));
+
+    let mut board = MAP1.clone();
+    board[12] = board[5].clone();
+    let content = serde_json::to_string(&board).unwrap();
+    assert_eq!(load_map(&content).unwrap_err(), MapError::RailroadCount(5));
+
+    let mut board = MAP1.clone();
+    if let Tile::Property { name, .. } = &mut board[39] {
+        *name = "Mayfair".to_owned();
+    }
+    let content = serde_json::to_string(&board).unwrap();
+    assert_eq!(
+        load_map(&content).unwrap_err(),
+        MapError::CardTarget("Advance to Boardwalk".to_owned())
+    );
+}
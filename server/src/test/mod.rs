@@ -1,8 +1,52 @@
 #[cfg(test)]
 mod action_tests;
 #[cfg(test)]
+mod auction_tests;
+#[cfg(test)]
+mod building_tests;
+#[cfg(test)]
+mod cards_tests;
+#[cfg(test)]
 mod communication_tests;
 #[cfg(test)]
+mod debt_tests;
+#[cfg(all(test, feature = "debug-commands"))]
+mod debug_tests;
+#[cfg(test)]
+mod dice_tests;
+#[cfg(test)]
 mod game_state_tests;
 #[cfg(test)]
+mod handshake_tests;
+#[cfg(test)]
+mod jackpot_tests;
+#[cfg(test)]
+mod jail_tests;
+#[cfg(test)]
+mod map_tests;
+#[cfg(test)]
+mod mortgage_tests;
+#[cfg(test)]
+mod rules_tests;
+#[cfg(test)]
 mod server_state_tests;
+#[cfg(test)]
+mod tax_tests;
+#[cfg(test)]
+mod trade_tests;
+
+#[cfg(test)]
+use crate::game_state::Player;
+#[cfg(test)]
+use tokio::sync::mpsc;
+#[cfg(test)]
+use uuid::Uuid;
+
+// Tests set the fields they care about with struct update syntax
+#[cfg(test)]
+pub(crate) fn test_player(id: Uuid, tx: mpsc::Sender<String>) -> Player {
+    Player {
+        id,
+        ..Player::default(tx, "TestPlayer".to_owned())
+    }
+}
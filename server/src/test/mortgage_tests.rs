@@ -0,0 +1,76 @@
+use crate::action::land_on_tile;
+use crate::game_state::{Game, Player};
+use crate::mortgage::{mortgage, unmortgage, MortgageError};
+use crate::test::test_player;
+use shared::board::{PropertyLevel, Tile};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+fn game_with_owner(owned: &[usize]) -> (Game, Uuid) {
+    let mut game = Game::default();
+    let player_id = Uuid::new_v4();
+    let (tx, _) = mpsc::channel(32);
+    game.players = vec![test_player(player_id, tx)];
+    for position in owned {
+        match &mut game.board[*position] {
+            Tile::Property { owner, .. }
+            | Tile::Railroad { owner, .. }
+            | Tile::Utility { owner, .. } => *owner = Some(player_id),
+            _ => {}
+        }
+    }
+    (game, player_id)
+}
+
+#[tokio::test]
+async fn mortgage_and_unmortgage() {
+    let (mut game, player_id) = game_with_owner(&[5]);
+
+    assert!(mortgage(player_id, &mut game, 5).await.is_ok());
+    assert!(game.board[5].is_mortgaged());
+    assert_eq!(game.players[0].money, 1600);
+    assert_eq!(
+        mortgage(player_id, &mut game, 5).await,
+        Err(MortgageError::AlreadyMortgaged)
+    );
+
+    // Lifting the mortgage costs 10% interest
+    assert!(unmortgage(player_id, &mut game, 5).await.is_ok());
+    assert!(!game.board[5].is_mortgaged());
+    assert_eq!(game.players[0].money, 1490);
+}
+
+#[tokio::test]
+async fn mortgage_requires_ownership_and_no_buildings() {
+    let (mut game, player_id) = game_with_owner(&[1, 3]);
+    if let Tile::Property { level, .. } = &mut game.board[3] {
+        *level = PropertyLevel::House1;
+    }
+
+    assert_eq!(
+        mortgage(player_id, &mut game, 6).await,
+        Err(MortgageError::NotOwned)
+    );
+    assert_eq!(
+        mortgage(player_id, &mut game, 1).await,
+        Err(MortgageError::HasBuildings)
+    );
+}
+
+#[tokio::test]
+async fn mortgaged_tile_collects_no_rent() {
+    let (mut game, owner_id) = game_with_owner(&[5]);
+    let player_id = Uuid::new_v4();
+    let (tx, _) = mpsc::channel(32);
+    game.players.push(Player {
+        position: 5,
+        ..test_player(player_id, tx)
+    });
+    game.player_turn = 1;
+    assert!(mortgage(owner_id, &mut game, 5).await.is_ok());
+
+    land_on_tile(&mut game, &player_id, 5).await;
+
+    assert_eq!(game.players[1].money, 1500);
+    assert_eq!(game.players[0].money, 1600);
+}
@@ -0,0 +1,39 @@
+use shared::rules::{GameRules, RulesError};
+
+#[test]
+fn default_rules_are_valid() {
+    assert_eq!(GameRules::default().validate(), Ok(()));
+}
+
+#[test]
+fn invalid_rules_are_rejected() {
+    let rules = GameRules {
+        jail_turns: 0,
+        ..GameRules::default()
+    };
+    assert_eq!(rules.validate(), Err(RulesError::NoJailTurns));
+
+    let rules = GameRules {
+        income_tax_percent: 150,
+        ..GameRules::default()
+    };
+    assert_eq!(rules.validate(), Err(RulesError::IncomeTaxPercent(150)));
+
+    let rules = GameRules {
+        utility_multipliers: [10, 4],
+        ..GameRules::default()
+    };
+    assert_eq!(rules.validate(), Err(RulesError::UtilityMultipliers));
+
+    let rules = GameRules {
+        max_turns: Some(0),
+        ..GameRules::default()
+    };
+    assert_eq!(rules.validate(), Err(RulesError::NoMaxTurns));
+
+    let rules = GameRules {
+        time_limit_minutes: Some(0),
+        ..GameRules::default()
+    };
+    assert_eq!(rules.validate(), Err(RulesError::NoTimeLimit));
+}
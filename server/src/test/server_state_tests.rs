@@ -1,5 +1,7 @@
-use crate::game_state::Player;
 use crate::server_state::ServerState;
+use crate::test::test_player;
+use shared::maps::map1::MAP1;
+use shared::rules::GameRules;
 use std::sync::Arc;
 use tokio::sync::mpsc;
 use tokio_test::block_on;
@@ -7,7 +9,7 @@ use uuid::Uuid;
 
 #[test]
 fn server_state_creation() {
-    let state = ServerState::new();
+    let state = ServerState::new(GameRules::default(), MAP1.clone());
 
     // Verify waiting room is empty
     assert!(block_on(state.waiting_room.lock()).players.is_empty());
@@ -18,23 +20,16 @@ fn server_state_creation() {
 
 #[tokio::test]
 async fn server_state_concurrent_access() {
-    let state = Arc::new(ServerState::new());
+    let state = Arc::new(ServerState::new(GameRules::default(), MAP1.clone()));
     let state_clone = Arc::clone(&state);
 
     // Spawn a task to modify the waiting room
     let task1 = tokio::spawn(async move {
         let mut waiting_room = state_clone.waiting_room.lock().await;
         // Perform some modification
-        waiting_room.players.push(Player {
-            id: Uuid::new_v4(),
-            name: "ConcurrentPlayer".to_owned(),
-            tx: mpsc::channel(1).0,
-            money: 1500,
-            position: 0,
-            is_in_jail: false,
-            jail_turns: 0,
-            is_bankrupt: false,
-        });
+        waiting_room
+            .players
+            .push(test_player(Uuid::new_v4(), mpsc::channel(1).0));
     });
 
     // Spawn another task to read from the waiting room
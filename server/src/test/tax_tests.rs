@@ -0,0 +1,54 @@
+use crate::action::land_on_tile;
+use crate::game_state::{Game, Player};
+use crate::tax::{choose_income_tax, TaxError};
+use crate::test::test_player;
+use shared::action::IncomeTaxChoice;
+use shared::board::net_worth;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+fn taxed_game() -> (Game, Uuid) {
+    let mut game = Game::default();
+    let player_id = Uuid::new_v4();
+    let (tx, _) = mpsc::channel(32);
+    game.players = vec![Player {
+        position: 4,
+        ..test_player(player_id, tx)
+    }];
+    game.rules.income_tax_choice = true;
+    (game, player_id)
+}
+
+#[tokio::test]
+async fn net_worth_counts_tiles_and_buildings() {
+    let (mut game, player_id) = taxed_game();
+    game.board[5].set_owner(Some(player_id));
+    game.board[12].set_owner(Some(player_id));
+    game.board[12].set_mortgaged(true);
+
+    // Railroad price plus half the utility price
+    assert_eq!(net_worth(&game.board, player_id, 1500), 1500 + 200 + 75);
+}
+
+#[tokio::test]
+async fn income_tax_waits_for_the_choice() {
+    let (mut game, player_id) = taxed_game();
+
+    land_on_tile(&mut game, &player_id, 4).await;
+    let tax = game.income_tax.as_ref().unwrap();
+    assert_eq!((tax.flat, tax.rate), (200, 10));
+    assert_eq!(game.players[0].money, 1500);
+
+    // The share is worked out from the net worth at the time of the choice
+    game.board[5].set_owner(Some(player_id));
+    assert!(
+        choose_income_tax(&mut game, player_id, IncomeTaxChoice::Percent)
+            .await
+            .is_ok()
+    );
+    assert_eq!(game.players[0].money, 1330);
+    assert_eq!(
+        choose_income_tax(&mut game, player_id, IncomeTaxChoice::Flat).await,
+        Err(TaxError::NoTaxDue)
+    );
+}
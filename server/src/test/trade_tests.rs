@@ -0,0 +1,210 @@
+use crate::game_state::Game;
+use crate::test::test_player;
+use crate::trade::{accept_trade, counter_trade, propose_trade, reject_trade, TradeError};
+use shared::action::{TradeAssets, TradeOffer};
+use shared::board::{PropertyLevel, Tile};
+use shared::cards::CardDeck;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+fn test_game() -> (Game, Uuid, Uuid) {
+    let mut game = Game::default();
+    let first_id = Uuid::new_v4();
+    let second_id = Uuid::new_v4();
+    let (tx1, _) = mpsc::channel(32);
+    let (tx2, _) = mpsc::channel(32);
+    game.players = vec![test_player(first_id, tx1), test_player(second_id, tx2)];
+    game.board[1].set_owner(Some(first_id));
+    game.board[5].set_owner(Some(second_id));
+    (game, first_id, second_id)
+}
+
+fn offer(from: Uuid, to: Uuid, offered: TradeAssets, requested: TradeAssets) -> TradeOffer {
+    TradeOffer {
+        id: Uuid::nil(),
+        from,
+        to,
+        offered,
+        requested,
+    }
+}
+
+#[tokio::test]
+async fn accepted_trade_swaps_assets() {
+    let (mut game, first_id, second_id) = test_game();
+    let trade = offer(
+        first_id,
+        second_id,
+        TradeAssets {
+            money: 100,
+            tiles: vec![1],
+            jail_cards: 0,
+        },
+        TradeAssets {
+            money: 0,
+            tiles: vec![5],
+            jail_cards: 0,
+        },
+    );
+    assert!(propose_trade(&mut game, first_id, trade).await.is_ok());
+    let trade_id = game.trades[0].id;
+
+    // Only the receiver can accept
+    assert_eq!(
+        accept_trade(&mut game, first_id, trade_id).await,
+        Err(TradeError::NotYourTrade)
+    );
+    assert!(accept_trade(&mut game, second_id, trade_id).await.is_ok());
+
+    assert!(game.trades.is_empty());
+    assert_eq!(game.board[1].owner(), Some(second_id));
+    assert_eq!(game.board[5].owner(), Some(first_id));
+    assert_eq!(game.players[0].money, 1400);
+    assert_eq!(game.players[1].money, 1600);
+}
+
+#[tokio::test]
+async fn trade_checks_ownership_on_accept() {
+    let (mut game, first_id, second_id) = test_game();
+    let trade = offer(
+        first_id,
+        second_id,
+        TradeAssets {
+            money: 0,
+            tiles: vec![1],
+            jail_cards: 0,
+        },
+        TradeAssets {
+            money: 200,
+            tiles: vec![],
+            jail_cards: 0,
+        },
+    );
+    assert_eq!(
+        propose_trade(
+            &mut game,
+            first_id,
+            offer(
+                first_id,
+                second_id,
+                TradeAssets {
+                    money: 0,
+                    tiles: vec![5],
+                    jail_cards: 0,
+                },
+                TradeAssets::default(),
+            ),
+        )
+        .await,
+        Err(TradeError::NotOwned(5))
+    );
+    assert!(propose_trade(&mut game, first_id, trade).await.is_ok());
+    let trade_id = game.trades[0].id;
+
+    // The tile changed hands before the offer was accepted
+    game.board[1].set_owner(None);
+    assert_eq!(
+        accept_trade(&mut game, second_id, trade_id).await,
+        Err(TradeError::NotOwned(1))
+    );
+    assert!(game.trades.is_empty());
+    assert_eq!(game.players[1].money, 1500);
+}
+
+#[tokio::test]
+async fn trade_refuses_built_color_groups() {
+    let (mut game, first_id, second_id) = test_game();
+    game.board[3].set_owner(Some(first_id));
+    if let Tile::Property { level, .. } = &mut game.board[3] {
+        *level = PropertyLevel::House1;
+    }
+    let trade = offer(
+        first_id,
+        second_id,
+        TradeAssets {
+            money: 0,
+            tiles: vec![1],
+            jail_cards: 0,
+        },
+        TradeAssets::default(),
+    );
+
+    assert_eq!(
+        propose_trade(&mut game, first_id, trade).await,
+        Err(TradeError::HasBuildings(1))
+    );
+}
+
+#[tokio::test]
+async fn counter_and_reject_trade() {
+    let (mut game, first_id, second_id) = test_game();
+    let trade = offer(
+        first_id,
+        second_id,
+        TradeAssets {
+            money: 50,
+            tiles: vec![],
+            jail_cards: 0,
+        },
+        TradeAssets {
+            money: 0,
+            tiles: vec![5],
+            jail_cards: 0,
+        },
+    );
+    assert!(propose_trade(&mut game, first_id, trade).await.is_ok());
+    let mut counter = offer(
+        second_id,
+        first_id,
+        TradeAssets {
+            money: 0,
+            tiles: vec![5],
+            jail_cards: 0,
+        },
+        TradeAssets {
+            money: 150,
+            tiles: vec![],
+            jail_cards: 0,
+        },
+    );
+    counter.id = game.trades[0].id;
+
+    assert!(counter_trade(&mut game, second_id, counter).await.is_ok());
+    assert_eq!(game.trades.len(), 1);
+    assert_eq!(game.trades[0].from, second_id);
+
+    let trade_id = game.trades[0].id;
+    assert!(reject_trade(&mut game, first_id, trade_id).await.is_ok());
+    assert!(game.trades.is_empty());
+}
+
+#[tokio::test]
+async fn jail_cards_can_be_traded() {
+    let (mut game, first_id, second_id) = test_game();
+    let trade = offer(
+        first_id,
+        second_id,
+        TradeAssets {
+            money: 0,
+            tiles: vec![],
+            jail_cards: 1,
+        },
+        TradeAssets {
+            money: 50,
+            tiles: vec![],
+            jail_cards: 0,
+        },
+    );
+    assert_eq!(
+        propose_trade(&mut game, first_id, trade.clone()).await,
+        Err(TradeError::NotEnoughJailCards)
+    );
+
+    game.players[0].jail_cards.push(CardDeck::CommunityChest);
+    assert!(propose_trade(&mut game, first_id, trade).await.is_ok());
+    let trade_id = game.trades[0].id;
+    assert!(accept_trade(&mut game, second_id, trade_id).await.is_ok());
+    assert!(game.players[0].jail_cards.is_empty());
+    assert_eq!(game.players[1].jail_cards, vec![CardDeck::CommunityChest]);
+    assert_eq!(game.players[0].money, 1550);
+}
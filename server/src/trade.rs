@@ -0,0 +1,198 @@
+use crate::communication::send_to_all_players;
+use crate::error::GameError;
+use crate::game_state::Game;
+use shared::action::{ErrorCode, ServerEvent, TradeAssets, TradeOffer};
+use shared::board::{PropertyLevel, Tile};
+use std::fmt;
+use uuid::Uuid;
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum TradeError {
+    InvalidOffer,
+    UnknownTrade,
+    NotYourTrade,
+    NotOwned(u32),
+    HasBuildings(u32),
+    NotEnoughMoney,
+    NotEnoughJailCards,
+}
+
+impl fmt::Display for TradeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TradeError::InvalidOffer => write!(f, "This trade offer is not valid"),
+            TradeError::UnknownTrade => write!(f, "This trade offer does not exist anymore"),
+            TradeError::NotYourTrade => write!(f, "This trade offer is not addressed to you"),
+            TradeError::NotOwned(position) => {
+                write!(f, "Tile {position} is not owned by the trader")
+            }
+            TradeError::HasBuildings(position) => {
+                write!(
+                    f,
+                    "Sell the buildings of the group of tile {position} first"
+                )
+            }
+            TradeError::NotEnoughMoney => write!(f, "Not enough money for this trade"),
+            TradeError::NotEnoughJailCards => {
+                write!(f, "Not enough Get Out of Jail Free cards for this trade")
+            }
+        }
+    }
+}
+
+impl GameError for TradeError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            TradeError::NotEnoughMoney => ErrorCode::InsufficientFunds,
+            _ => ErrorCode::IllegalMove,
+        }
+    }
+}
+
+// Check that the player still owns everything they put in the trade
+fn check_assets(game: &Game, owner: Uuid, assets: &TradeAssets) -> Result<(), TradeError> {
+    let Some(player) = game
+        .players
+        .iter()
+        .find(|p| p.id == owner && !p.is_bankrupt)
+    else {
+        return Err(TradeError::InvalidOffer);
+    };
+    if player.money < assets.money {
+        return Err(TradeError::NotEnoughMoney);
+    }
+    if (player.jail_cards.len() as u32) < assets.jail_cards {
+        return Err(TradeError::NotEnoughJailCards);
+    }
+    for position in &assets.tiles {
+        let Some(tile) = game.board.get(*position as usize) else {
+            return Err(TradeError::NotOwned(*position));
+        };
+        if tile.owner() != Some(owner) {
+            return Err(TradeError::NotOwned(*position));
+        }
+        // Properties change hands only when their color group has no buildings
+        if let Tile::Property { group, .. } = tile {
+            if game.board.iter().any(|other| {
+                matches!(other, Tile::Property { group: other_group, level, .. }
+                    if other_group == group && *level != PropertyLevel::None)
+            }) {
+                return Err(TradeError::HasBuildings(*position));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_offer(game: &Game, offer: &TradeOffer) -> Result<(), TradeError> {
+    if offer.from == offer.to {
+        return Err(TradeError::InvalidOffer);
+    }
+    check_assets(game, offer.from, &offer.offered)?;
+    check_assets(game, offer.to, &offer.requested)
+}
+
+pub(crate) async fn propose_trade(
+    game: &mut Game,
+    uuid: Uuid,
+    mut offer: TradeOffer,
+) -> Result<(), TradeError> {
+    if offer.from != uuid {
+        return Err(TradeError::InvalidOffer);
+    }
+    check_offer(game, &offer)?;
+    offer.id = Uuid::new_v4();
+    log::debug!("Player {uuid} proposed trade {} to {}", offer.id, offer.to);
+    game.trades.push(offer.clone());
+    send_to_all_players(&game.players, ServerEvent::ProposeTrade(offer)).await;
+    Ok(())
+}
+
+pub(crate) async fn counter_trade(
+    game: &mut Game,
+    uuid: Uuid,
+    mut offer: TradeOffer,
+) -> Result<(), TradeError> {
+    let Some(index) = game.trades.iter().position(|trade| trade.id == offer.id) else {
+        return Err(TradeError::UnknownTrade);
+    };
+    let previous = &game.trades[index];
+    if previous.to != uuid || offer.from != uuid || offer.to != previous.from {
+        return Err(TradeError::NotYourTrade);
+    }
+    check_offer(game, &offer)?;
+    game.trades.remove(index);
+    offer.id = Uuid::new_v4();
+    log::debug!("Player {uuid} countered with trade {}", offer.id);
+    game.trades.push(offer.clone());
+    send_to_all_players(&game.players, ServerEvent::CounterTrade(offer)).await;
+    Ok(())
+}
+
+pub(crate) async fn accept_trade(
+    game: &mut Game,
+    uuid: Uuid,
+    trade_id: Uuid,
+) -> Result<(), TradeError> {
+    let Some(index) = game.trades.iter().position(|trade| trade.id == trade_id) else {
+        return Err(TradeError::UnknownTrade);
+    };
+    if game.trades[index].to != uuid {
+        return Err(TradeError::NotYourTrade);
+    }
+    let offer = game.trades.remove(index);
+    // Both sides may have spent or lost assets since the offer was made
+    if let Err(e) = check_offer(game, &offer) {
+        send_to_all_players(&game.players, ServerEvent::RejectTrade(offer)).await;
+        return Err(e);
+    }
+    for player in game.players.iter_mut() {
+        if player.id == offer.from {
+            player.money = player.money - offer.offered.money + offer.requested.money;
+        } else if player.id == offer.to {
+            player.money = player.money - offer.requested.money + offer.offered.money;
+        }
+    }
+    give_jail_cards(game, offer.from, offer.to, offer.offered.jail_cards);
+    give_jail_cards(game, offer.to, offer.from, offer.requested.jail_cards);
+    for position in &offer.offered.tiles {
+        game.board[*position as usize].set_owner(Some(offer.to));
+    }
+    for position in &offer.requested.tiles {
+        game.board[*position as usize].set_owner(Some(offer.from));
+    }
+    log::debug!(
+        "Trade {} between {} and {} completed",
+        offer.id,
+        offer.from,
+        offer.to
+    );
+    send_to_all_players(&game.players, ServerEvent::TradeCompleted(offer)).await;
+    Ok(())
+}
+
+fn give_jail_cards(game: &mut Game, from: Uuid, to: Uuid, count: u32) {
+    let giver = game.players.iter_mut().find(|p| p.id == from).unwrap();
+    let kept = giver.jail_cards.len() - count as usize;
+    let cards: Vec<_> = giver.jail_cards.drain(kept..).collect();
+    let receiver = game.players.iter_mut().find(|p| p.id == to).unwrap();
+    receiver.jail_cards.extend(cards);
+}
+
+pub(crate) async fn reject_trade(
+    game: &mut Game,
+    uuid: Uuid,
+    trade_id: Uuid,
+) -> Result<(), TradeError> {
+    let Some(index) = game.trades.iter().position(|trade| trade.id == trade_id) else {
+        return Err(TradeError::UnknownTrade);
+    };
+    // The proposer can withdraw the offer, the other side can reject it
+    if game.trades[index].to != uuid && game.trades[index].from != uuid {
+        return Err(TradeError::NotYourTrade);
+    }
+    let offer = game.trades.remove(index);
+    log::debug!("Player {uuid} rejected trade {}", offer.id);
+    send_to_all_players(&game.players, ServerEvent::RejectTrade(offer)).await;
+    Ok(())
+}
@@ -1,67 +1,362 @@
+use crate::board::{PropertyLevel, Tile};
+use crate::cards::{Card, CardDeck};
+use crate::rules::GameRules;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub enum Action {
-    GameStart,
-    PlayerTurn,
-    Identify,
+// Messages sent by the server, each one carries its payload
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "event", content = "data")]
+pub enum ServerEvent {
+    // The handshake succeeded, carries the id given to the player
+    Welcome(WelcomeData),
+    // The handshake failed, the server closes the connection
+    HandshakeRejected(HandshakeRejectedData),
+    GameStart(GameStartData),
+    PlayerTurn(Uuid),
+    Roll(DiceRollData),
+    // New position of the current player
+    Move(usize),
+    PayRent(PayRentData),
+    AskBuyProperty(BuyPropertyData),
+    BuyProperty(BuyPropertyData),
+    SkipBuyProperty(Uuid),
+    GoToJail(Uuid),
+    PlayerGoTile(PlayerGoTileData),
+    PassGo(PlayerGoTileData),
+    FreeFromJail(Uuid),
+    PayJailFine(JailData),
+    UseJailCard(JailData),
+    PayTax(PlayerPayTaxData),
+    AskIncomeTax(IncomeTaxData),
+    PlayerBankrupt(BankruptData),
+    GameOver(GameOverData),
+    DrawCard(DrawCardData),
+    BuildHouse(BuildingData),
+    SellHouse(BuildingData),
+    // A house was asked for during a shortage, other players can ask for one until it closes
+    RequestHouse(HouseRequestData),
+    Mortgage(MortgageData),
+    Unmortgage(MortgageData),
+    AuctionStart(AuctionStartData),
+    PlaceBid(BidData),
+    AuctionEnd(AuctionEndData),
+    ProposeTrade(TradeOffer),
+    CounterTrade(TradeOffer),
+    RejectTrade(TradeOffer),
+    TradeCompleted(TradeOffer),
+    DebtOutstanding(DebtData),
+    PayDebt(DebtData),
+    Jackpot(JackpotData),
+    // A developer command changed the game, the clients take the money and owners as they are
+    StateRefresh(StateRefreshData),
+    // The command was refused, the message explains why
+    Error(ErrorData),
+}
+
+// Messages sent by the clients, each one carries its payload
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "command", content = "data")]
+pub enum ClientCommand {
+    // First command of a connection, names the player and the client's protocol
+    Identify(IdentifyData),
     Roll,
-    TimeToPlay,
-    PayRent,
-    AskBuyProperty,
     BuyProperty,
     SkipBuyProperty,
-    Move,
-    Invalid,
+    // Commands on a tile carry its position
+    BuildHouse(usize),
+    SellHouse(usize),
+    Mortgage(usize),
+    Unmortgage(usize),
+    PlaceBid(u32),
+    ProposeTrade(TradeOffer),
+    CounterTrade(TradeOffer),
+    // Trade commands on an offer carry its id
+    AcceptTrade(Uuid),
+    RejectTrade(Uuid),
+    DeclareBankruptcy,
+    PayJailFine,
+    UseJailCard,
+    ChooseIncomeTax(IncomeTaxChoice),
+    // Steps moved by the bus
+    TakeBus(u8),
+    // Tile picked after a triple
+    Teleport(usize),
+    #[cfg(feature = "debug-commands")]
+    Debug(DebugCommand),
+}
+
+// Developer commands, only servers built with the debug-commands feature run them
+#[cfg(feature = "debug-commands")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "command", content = "data")]
+pub enum DebugCommand {
+    // The player owns every property
     BuyAll,
-    GoToJail,
-    PlayerGoTile,
-    FreeFromJail,
-    PayTax,
-    PlayerBankrupt,
-    GameOver,
+    SetMoney(u32),
+    // Moves the player without resolving the tile
+    Teleport(usize),
+    // Dice of the next roll
+    ForceDice(ForcedRollData),
+    // Get Out of Jail Free card of the deck
+    GiveCard(CardDeck),
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct PlayerAction {
-    pub action_type: Action,
-    pub data: Option<String>,
+#[cfg(feature = "debug-commands")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ForcedRollData {
+    pub dice1: u8,
+    pub dice2: u8,
+    #[serde(default)]
+    pub speed_die: Option<SpeedDieFace>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct IdentifyData {
+    pub name: String,
+    pub protocol_version: u32,
+    #[serde(default)]
+    pub capabilities: Vec<String>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WelcomeData {
+    pub player: Uuid,
+    pub protocol_version: u32,
+    pub capabilities: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HandshakeRejectedData {
+    // Version the server speaks
+    pub protocol_version: u32,
+    pub reason: String,
+}
+
+// Why a command was refused
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum ErrorCode {
+    MalformedMessage,
+    NotYourTurn,
+    InsufficientFunds,
+    IllegalMove,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ErrorData {
+    pub code: ErrorCode,
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PayRentData {
     pub rent: u32,
     pub owner: Uuid,
     pub player: Uuid,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct BuyPropertyData {
     pub position: u32,
     pub player: Uuid,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PlayerGoTileData {
     pub player: Uuid,
     pub amount: u32,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct DiceRollData {
     pub dice1: u8,
     pub dice2: u8,
+    // The player rolled doubles and rolls again
+    #[serde(default)]
+    pub extra_roll: bool,
+    // Third die of the speed die rule
+    #[serde(default)]
+    pub speed_die: Option<SpeedDieFace>,
+}
+
+// Faces of the speed die, Mr. Monopoly is printed on two of them
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum SpeedDieFace {
+    Number(u8),
+    MrMonopoly,
+    Bus,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct PlayerIdentifyData {
+pub struct PlayerStartData {
     pub id: Uuid,
     pub name: String,
+    pub money: u32,
+    pub position: usize,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PlayerPayTaxData {
     pub player: Uuid,
     pub amount: u32,
 }
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DrawCardData {
+    pub player: Uuid,
+    pub deck: CardDeck,
+    pub card: Card,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BuildingData {
+    pub player: Uuid,
+    pub position: u32,
+    pub level: PropertyLevel,
+    pub price: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HouseRequestData {
+    pub player: Uuid,
+    pub position: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum AuctionItem {
+    House,
+    Property(u32),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AuctionStartData {
+    pub item: AuctionItem,
+    pub bidders: Vec<Uuid>,
+    pub starting_price: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BidData {
+    pub player: Uuid,
+    pub amount: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AuctionEndData {
+    pub item: AuctionItem,
+    pub winner: Option<Uuid>,
+    pub amount: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MortgageData {
+    pub player: Uuid,
+    pub position: u32,
+    pub amount: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct TradeAssets {
+    pub money: u32,
+    pub tiles: Vec<u32>,
+    #[serde(default)]
+    pub jail_cards: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TradeOffer {
+    // Set by the server, a counter-offer carries the id of the offer it answers
+    pub id: Uuid,
+    pub from: Uuid,
+    pub to: Uuid,
+    pub offered: TradeAssets,
+    pub requested: TradeAssets,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DebtData {
+    pub debtor: Uuid,
+    // None when the debt is owed to the bank
+    pub creditor: Option<Uuid>,
+    pub amount: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BankruptData {
+    pub player: Uuid,
+    // None when the player went bankrupt to the bank
+    pub creditor: Option<Uuid>,
+    // Cash handed to the creditor, buildings included at their resale value
+    pub money: u32,
+    pub tiles: Vec<u32>,
+    // Get Out of Jail Free cards handed to the creditor
+    pub jail_cards: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct JailData {
+    pub player: Uuid,
+    pub fine: u32,
+    // Deck the used Get Out of Jail Free card goes back to
+    pub card: Option<CardDeck>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GameStartData {
+    pub players: Vec<PlayerStartData>,
+    pub rules: GameRules,
+    // The clients build their board from it
+    pub board: Vec<Tile>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StateRefreshData {
+    pub players: Vec<PlayerStartData>,
+    pub board: Vec<Tile>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct JackpotData {
+    // Free Parking pot after the change
+    pub pot: u32,
+    // Player who landed on Free Parking, None when money was added to the pot
+    pub collected_by: Option<Uuid>,
+    pub amount: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct IncomeTaxData {
+    pub player: Uuid,
+    pub flat: u32,
+    // Percentage of the net worth the player can pay instead, worked out when they choose
+    pub rate: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum IncomeTaxChoice {
+    Flat,
+    Percent,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum GameOverReason {
+    LastPlayerStanding,
+    TurnLimit,
+    TimeLimit,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Standing {
+    pub player: Uuid,
+    pub money: u32,
+    pub net_worth: u32,
+    pub is_bankrupt: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GameOverData {
+    pub winner: Uuid,
+    pub reason: GameOverReason,
+    // Best net worth first, bankrupt players last
+    pub standings: Vec<Standing>,
+}
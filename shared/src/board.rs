@@ -1,7 +1,9 @@
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub enum PropertyLevel {
+    #[default]
     None = 0,
     House1 = 1,
     House2 = 2,
@@ -10,14 +12,47 @@ pub enum PropertyLevel {
     Hotel = 5,
 }
 
-#[derive(Debug, Clone)]
+impl PropertyLevel {
+    pub fn from_index(index: usize) -> Option<Self> {
+        match index {
+            0 => Some(PropertyLevel::None),
+            1 => Some(PropertyLevel::House1),
+            2 => Some(PropertyLevel::House2),
+            3 => Some(PropertyLevel::House3),
+            4 => Some(PropertyLevel::House4),
+            5 => Some(PropertyLevel::Hotel),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ColorGroup {
+    Brown,
+    LightBlue,
+    Pink,
+    Orange,
+    Red,
+    Yellow,
+    Green,
+    DarkBlue,
+}
+
+// Map files can leave out the game state fields: level, owner and mortgaged
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Tile {
+    // costs[0] is the purchase price and costs[1] the price of a house
     Property {
         name: String,
         costs: Vec<u32>,
         rents: Vec<u32>,
+        #[serde(default)]
         level: PropertyLevel,
+        #[serde(default)]
         owner: Option<Uuid>,
+        group: ColorGroup,
+        #[serde(default)]
+        mortgaged: bool,
     },
     Chance(String),
     Jail,
@@ -27,13 +62,19 @@ pub enum Tile {
     },
     FreeParking,
     Railroad {
+        #[serde(default)]
         owner: Option<Uuid>,
         cost: u32,
         rents: Vec<u32>,
+        #[serde(default)]
+        mortgaged: bool,
     },
     Utility {
         cost: u32,
+        #[serde(default)]
         owner: Option<Uuid>,
+        #[serde(default)]
+        mortgaged: bool,
     },
     Tax {
         price: u32,
@@ -42,3 +83,124 @@ pub enum Tile {
         price: u32,
     },
 }
+
+impl Tile {
+    // Price to buy the tile from the bank, None for tiles that can't be owned
+    pub fn price(&self) -> Option<u32> {
+        match self {
+            Tile::Property { costs, .. } => Some(costs[0]),
+            Tile::Railroad { cost, .. } | Tile::Utility { cost, .. } => Some(*cost),
+            _ => None,
+        }
+    }
+
+    pub fn owner(&self) -> Option<Uuid> {
+        match self {
+            Tile::Property { owner, .. }
+            | Tile::Railroad { owner, .. }
+            | Tile::Utility { owner, .. } => *owner,
+            _ => None,
+        }
+    }
+
+    pub fn set_owner(&mut self, new_owner: Option<Uuid>) {
+        match self {
+            Tile::Property { owner, .. }
+            | Tile::Railroad { owner, .. }
+            | Tile::Utility { owner, .. } => *owner = new_owner,
+            _ => {}
+        }
+    }
+
+    pub fn is_mortgaged(&self) -> bool {
+        matches!(
+            self,
+            Tile::Property {
+                mortgaged: true,
+                ..
+            } | Tile::Railroad {
+                mortgaged: true,
+                ..
+            } | Tile::Utility {
+                mortgaged: true,
+                ..
+            }
+        )
+    }
+
+    pub fn set_mortgaged(&mut self, value: bool) {
+        match self {
+            Tile::Property { mortgaged, .. }
+            | Tile::Railroad { mortgaged, .. }
+            | Tile::Utility { mortgaged, .. } => *mortgaged = value,
+            _ => {}
+        }
+    }
+
+    // The bank lends half the price of the tile
+    pub fn mortgage_value(&self) -> Option<u32> {
+        self.price().map(|price| price / 2)
+    }
+
+    // Lifting a mortgage costs its value plus 10% interest
+    pub fn unmortgage_cost(&self) -> Option<u32> {
+        self.mortgage_value().map(|value| value + value / 10)
+    }
+}
+
+// Count the houses and hotels owned by a player, used for repair costs
+pub fn count_buildings(board: &[Tile], player: Uuid) -> (u32, u32) {
+    let mut houses = 0;
+    let mut hotels = 0;
+    for tile in board {
+        if let Tile::Property {
+            level,
+            owner: Some(owner),
+            ..
+        } = tile
+        {
+            if *owner != player {
+                continue;
+            }
+            match level {
+                PropertyLevel::Hotel => hotels += 1,
+                _ => houses += level.clone() as u32,
+            }
+        }
+    }
+    (houses, hotels)
+}
+
+// Cash plus the price of every tile owned and the cost of their buildings, mortgaged tiles
+// count for their mortgage value
+pub fn net_worth(board: &[Tile], player: Uuid, money: u32) -> u32 {
+    let assets: u32 = board
+        .iter()
+        .filter(|tile| tile.owner() == Some(player))
+        .map(|tile| {
+            let value = if tile.is_mortgaged() {
+                tile.mortgage_value()
+            } else {
+                tile.price()
+            };
+            let buildings = match tile {
+                Tile::Property { level, costs, .. } => level.clone() as u32 * costs[1],
+                _ => 0,
+            };
+            value.unwrap_or(0) + buildings
+        })
+        .sum();
+    money + assets
+}
+
+// A player has a monopoly when every property of the group belongs to them
+pub fn owns_color_group(board: &[Tile], group: ColorGroup, player: Uuid) -> bool {
+    board.iter().all(|tile| match tile {
+        Tile::Property {
+            group: tile_group,
+            owner,
+            ..
+        } if *tile_group == group => *owner == Some(player),
+        _ => true,
+    })
+}
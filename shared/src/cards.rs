@@ -0,0 +1,150 @@
+use crate::board::Tile;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum CardDeck {
+    Chance,
+    CommunityChest,
+}
+
+impl CardDeck {
+    // Chance tiles are named after the deck they draw from
+    pub fn from_tile_name(name: &str) -> Self {
+        if name == "Chance" {
+            CardDeck::Chance
+        } else {
+            CardDeck::CommunityChest
+        }
+    }
+
+    pub fn cards(&self) -> &'static [Card] {
+        match self {
+            CardDeck::Chance => &CHANCE_CARDS,
+            CardDeck::CommunityChest => &COMMUNITY_CHEST_CARDS,
+        }
+    }
+}
+
+// Where a card sends the player, looked up on the board the game is played on
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum CardTarget {
+    Go,
+    Property(String),
+    // Counted from Go, starting at 0
+    Railroad(usize),
+}
+
+impl CardTarget {
+    pub fn position(&self, board: &[Tile]) -> Option<usize> {
+        match self {
+            CardTarget::Go => board
+                .iter()
+                .position(|tile| matches!(tile, Tile::Go { .. })),
+            CardTarget::Property(target) => board
+                .iter()
+                .position(|tile| matches!(tile, Tile::Property { name, .. } if name == target)),
+            CardTarget::Railroad(index) => board
+                .iter()
+                .enumerate()
+                .filter(|(_, tile)| matches!(tile, Tile::Railroad { .. }))
+                .nth(*index)
+                .map(|(position, _)| position),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum CardEffect {
+    Collect(u32),
+    Pay(u32),
+    MoveTo(CardTarget),
+    MoveBack(usize),
+    GoToJail,
+    // Kept by the player until used to leave jail
+    GetOutOfJailFree,
+    PayEachPlayer(u32),
+    CollectFromEachPlayer(u32),
+    Repairs { per_house: u32, per_hotel: u32 },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Card {
+    pub description: String,
+    pub effect: CardEffect,
+}
+
+impl Card {
+    fn new(description: &str, effect: CardEffect) -> Self {
+        Card {
+            description: description.to_owned(),
+            effect,
+        }
+    }
+}
+
+lazy_static! {
+    pub static ref CHANCE_CARDS: Vec<Card> = vec![
+        Card::new("Advance to Go", CardEffect::MoveTo(CardTarget::Go)),
+        Card::new(
+            "Advance to Illinois Avenue",
+            CardEffect::MoveTo(CardTarget::Property("Illinois Avenue".to_owned())),
+        ),
+        Card::new(
+            "Advance to St. Charles Place",
+            CardEffect::MoveTo(CardTarget::Property("St. Charles Place".to_owned())),
+        ),
+        Card::new(
+            "Take a trip to Reading Railroad",
+            CardEffect::MoveTo(CardTarget::Railroad(0)),
+        ),
+        Card::new(
+            "Advance to Boardwalk",
+            CardEffect::MoveTo(CardTarget::Property("Boardwalk".to_owned())),
+        ),
+        Card::new("Bank pays you dividend of 50", CardEffect::Collect(50)),
+        Card::new("Go back 3 spaces", CardEffect::MoveBack(3)),
+        Card::new("Go to Jail", CardEffect::GoToJail),
+        Card::new("Get Out of Jail Free", CardEffect::GetOutOfJailFree),
+        Card::new(
+            "Make general repairs on all your property",
+            CardEffect::Repairs {
+                per_house: 25,
+                per_hotel: 100,
+            },
+        ),
+        Card::new("Speeding fine", CardEffect::Pay(15)),
+        Card::new(
+            "You have been elected Chairman of the Board",
+            CardEffect::PayEachPlayer(50),
+        ),
+        Card::new("Your building loan matures", CardEffect::Collect(150)),
+    ];
+    pub static ref COMMUNITY_CHEST_CARDS: Vec<Card> = vec![
+        Card::new("Advance to Go", CardEffect::MoveTo(CardTarget::Go)),
+        Card::new("Bank error in your favor", CardEffect::Collect(200)),
+        Card::new("Doctor's fee", CardEffect::Pay(50)),
+        Card::new("From sale of stock you get 50", CardEffect::Collect(50)),
+        Card::new("Go to Jail", CardEffect::GoToJail),
+        Card::new("Get Out of Jail Free", CardEffect::GetOutOfJailFree),
+        Card::new("Holiday fund matures", CardEffect::Collect(100)),
+        Card::new("Income tax refund", CardEffect::Collect(20)),
+        Card::new("It is your birthday", CardEffect::CollectFromEachPlayer(10)),
+        Card::new("Life insurance matures", CardEffect::Collect(100)),
+        Card::new("Pay hospital fees", CardEffect::Pay(100)),
+        Card::new("Pay school fees", CardEffect::Pay(50)),
+        Card::new("Receive consultancy fee", CardEffect::Collect(25)),
+        Card::new(
+            "You are assessed for street repairs",
+            CardEffect::Repairs {
+                per_house: 40,
+                per_hotel: 115,
+            },
+        ),
+        Card::new(
+            "You have won second prize in a beauty contest",
+            CardEffect::Collect(10)
+        ),
+        Card::new("You inherit 100", CardEffect::Collect(100)),
+    ];
+}
@@ -1,4 +1,6 @@
 pub mod action;
 pub mod board;
+pub mod cards;
 pub mod list_const;
 pub mod maps;
+pub mod rules;
@@ -1 +1,9 @@
 pub const NUMBER_PLAYERS_PER_GAME: usize = 2;
+pub const BANK_HOUSES: u32 = 32;
+pub const BANK_HOTELS: u32 = 12;
+pub const AUCTION_MIN_INCREMENT: u32 = 10;
+pub const AUCTION_BID_SECONDS: u64 = 10;
+// Bumped on every change of the messages exchanged with the server
+pub const PROTOCOL_VERSION: u32 = 8;
+// Optional features a build supports, announced during the handshake
+pub const CAPABILITIES: [&str; 4] = ["auction", "trade", "speed_die", "income_tax_choice"];
@@ -0,0 +1,110 @@
+use crate::board::Tile;
+use crate::cards::{CardDeck, CardEffect};
+use std::fmt;
+
+// Rent with no house up to a hotel
+pub const PROPERTY_RENTS: usize = 6;
+// Rent for one to four railroads owned
+pub const RAILROAD_RENTS: usize = 4;
+// Purchase price and house price
+pub const PROPERTY_COSTS: usize = 2;
+
+#[derive(Debug, PartialEq)]
+pub enum MapError {
+    Parse(String),
+    NoGoAtStart,
+    JailCount(usize),
+    PropertyRents(usize),
+    PropertyCosts(usize),
+    RailroadRents(usize),
+    RailroadCount(usize),
+    CardTarget(String),
+}
+
+impl fmt::Display for MapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MapError::Parse(e) => write!(f, "Invalid map file: {e}"),
+            MapError::NoGoAtStart => write!(f, "The first tile must be a Go tile"),
+            MapError::JailCount(count) => {
+                write!(f, "A map needs exactly one Jail, found {count}")
+            }
+            MapError::PropertyRents(index) => {
+                write!(f, "Property {index} needs {PROPERTY_RENTS} rents")
+            }
+            MapError::PropertyCosts(index) => {
+                write!(f, "Property {index} needs {PROPERTY_COSTS} costs")
+            }
+            MapError::RailroadRents(index) => {
+                write!(f, "Railroad {index} needs {RAILROAD_RENTS} rents")
+            }
+            MapError::RailroadCount(count) => {
+                write!(
+                    f,
+                    "A map has at most {RAILROAD_RENTS} railroads, found {count}"
+                )
+            }
+            MapError::CardTarget(card) => {
+                write!(f, "The card \"{card}\" has no tile to move to on this map")
+            }
+        }
+    }
+}
+
+// Board definition as a JSON list of tiles
+pub fn load_map(content: &str) -> Result<Vec<Tile>, MapError> {
+    let board: Vec<Tile> =
+        serde_json::from_str(content).map_err(|e| MapError::Parse(e.to_string()))?;
+    validate_map(&board)?;
+    Ok(board)
+}
+
+pub fn validate_map(board: &[Tile]) -> Result<(), MapError> {
+    if !matches!(board.first(), Some(Tile::Go { .. })) {
+        return Err(MapError::NoGoAtStart);
+    }
+    let jails = board
+        .iter()
+        .filter(|tile| matches!(tile, Tile::Jail))
+        .count();
+    // Three doubles and jail cards send players to jail on any map
+    if jails != 1 {
+        return Err(MapError::JailCount(jails));
+    }
+    // The railroad rent is picked by the number of railroads owned
+    let railroads = board
+        .iter()
+        .filter(|tile| matches!(tile, Tile::Railroad { .. }))
+        .count();
+    if railroads > RAILROAD_RENTS {
+        return Err(MapError::RailroadCount(railroads));
+    }
+    // The decks drawn on this map must find every tile their cards move to
+    for tile in board {
+        let Tile::Chance(name) = tile else {
+            continue;
+        };
+        for card in CardDeck::from_tile_name(name).cards() {
+            if let CardEffect::MoveTo(target) = &card.effect {
+                if target.position(board).is_none() {
+                    return Err(MapError::CardTarget(card.description.clone()));
+                }
+            }
+        }
+    }
+    for (index, tile) in board.iter().enumerate() {
+        match tile {
+            Tile::Property { rents, .. } if rents.len() != PROPERTY_RENTS => {
+                return Err(MapError::PropertyRents(index));
+            }
+            Tile::Property { costs, .. } if costs.len() != PROPERTY_COSTS => {
+                return Err(MapError::PropertyCosts(index));
+            }
+            Tile::Railroad { rents, .. } if rents.len() != RAILROAD_RENTS => {
+                return Err(MapError::RailroadRents(index));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use crate::board::{PropertyLevel, Tile};
+use crate::board::{ColorGroup, PropertyLevel, Tile};
 use lazy_static::lazy_static;
 
 lazy_static! {
@@ -6,196 +6,246 @@ lazy_static! {
         Tile::Go { amount: 200 },
         Tile::Property {
             name: "Mediterranean Avenue".to_owned(),
-            costs: vec![60],
+            costs: vec![60, 50],
             rents: vec![2, 10, 30, 90, 160, 250],
             level: PropertyLevel::None,
             owner: None,
+            group: ColorGroup::Brown,
+            mortgaged: false,
         },
         Tile::Chance("Community Chest".to_owned()),
         Tile::Property {
             name: "Baltic Avenue".to_owned(),
-            costs: vec![60],
+            costs: vec![60, 50],
             rents: vec![4, 20, 60, 180, 320, 450],
             level: PropertyLevel::None,
             owner: None,
+            group: ColorGroup::Brown,
+            mortgaged: false,
         },
         Tile::Tax { price: 200 },
         Tile::Railroad {
             owner: None,
             cost: 200,
             rents: vec![25, 50, 100, 200],
+            mortgaged: false,
         },
         Tile::Property {
             name: "Oriental Avenue".to_owned(),
-            costs: vec![100],
+            costs: vec![100, 50],
             rents: vec![6, 30, 90, 270, 400, 550],
             level: PropertyLevel::None,
             owner: None,
+            group: ColorGroup::LightBlue,
+            mortgaged: false,
         },
         Tile::Chance("Chance".to_owned()),
         Tile::Property {
             name: "Vermont Avenue".to_owned(),
-            costs: vec![100],
+            costs: vec![100, 50],
             rents: vec![6, 30, 90, 270, 400, 550],
             level: PropertyLevel::None,
             owner: None,
+            group: ColorGroup::LightBlue,
+            mortgaged: false,
         },
         Tile::Property {
             name: "Connecticut Avenue".to_owned(),
-            costs: vec![120],
+            costs: vec![120, 50],
             rents: vec![8, 40, 100, 300, 450, 600],
             level: PropertyLevel::None,
             owner: None,
+            group: ColorGroup::LightBlue,
+            mortgaged: false,
         },
         Tile::Jail,
         Tile::Property {
             name: "St. Charles Place".to_owned(),
-            costs: vec![140],
+            costs: vec![140, 100],
             rents: vec![10, 50, 150, 450, 625, 750],
             level: PropertyLevel::None,
             owner: None,
+            group: ColorGroup::Pink,
+            mortgaged: false,
         },
         Tile::Utility {
             cost: 150,
             owner: None,
+            mortgaged: false,
         },
         Tile::Property {
             name: "States Avenue".to_owned(),
-            costs: vec![140],
+            costs: vec![140, 100],
             rents: vec![10, 50, 150, 450, 625, 750],
             level: PropertyLevel::None,
             owner: None,
+            group: ColorGroup::Pink,
+            mortgaged: false,
         },
         Tile::Property {
             name: "Virginia Avenue".to_owned(),
-            costs: vec![160],
+            costs: vec![160, 100],
             rents: vec![12, 60, 180, 500, 700, 900],
             level: PropertyLevel::None,
             owner: None,
+            group: ColorGroup::Pink,
+            mortgaged: false,
         },
         Tile::Railroad {
             owner: None,
             cost: 200,
             rents: vec![25, 50, 100, 200],
+            mortgaged: false,
         },
         Tile::Property {
             name: "St. James Place".to_owned(),
-            costs: vec![180],
+            costs: vec![180, 100],
             rents: vec![14, 70, 200, 550, 750, 950],
             level: PropertyLevel::None,
             owner: None,
+            group: ColorGroup::Orange,
+            mortgaged: false,
         },
         Tile::Chance("Community Chest".to_owned()),
         Tile::Property {
             name: "Tennessee Avenue".to_owned(),
-            costs: vec![180],
+            costs: vec![180, 100],
             rents: vec![14, 70, 200, 550, 750, 950],
             level: PropertyLevel::None,
             owner: None,
+            group: ColorGroup::Orange,
+            mortgaged: false,
         },
         Tile::Property {
             name: "New York Avenue".to_owned(),
-            costs: vec![200],
+            costs: vec![200, 100],
             rents: vec![16, 80, 220, 600, 800, 1000],
             level: PropertyLevel::None,
             owner: None,
+            group: ColorGroup::Orange,
+            mortgaged: false,
         },
         Tile::FreeParking,
         Tile::Property {
             name: "Kentucky Avenue".to_owned(),
-            costs: vec![220],
+            costs: vec![220, 150],
             rents: vec![18, 90, 250, 700, 875, 1050],
             level: PropertyLevel::None,
             owner: None,
+            group: ColorGroup::Red,
+            mortgaged: false,
         },
         Tile::Chance("Community Chest".to_owned()),
         Tile::Property {
             name: "Indiana Avenue".to_owned(),
-            costs: vec![220],
+            costs: vec![220, 150],
             rents: vec![18, 90, 250, 700, 875, 1050],
             level: PropertyLevel::None,
             owner: None,
+            group: ColorGroup::Red,
+            mortgaged: false,
         },
         Tile::Property {
             name: "Illinois Avenue".to_owned(),
-            costs: vec![240],
+            costs: vec![240, 150],
             rents: vec![20, 100, 300, 750, 925, 1100],
             level: PropertyLevel::None,
             owner: None,
+            group: ColorGroup::Red,
+            mortgaged: false,
         },
         Tile::Railroad {
             owner: None,
             cost: 200,
             rents: vec![25, 50, 100, 200],
+            mortgaged: false,
         },
         Tile::Property {
             name: "Atlantic Avenue".to_owned(),
-            costs: vec![260],
+            costs: vec![260, 150],
             rents: vec![22, 110, 330, 800, 975, 1150],
             level: PropertyLevel::None,
             owner: None,
+            group: ColorGroup::Yellow,
+            mortgaged: false,
         },
         Tile::Property {
             name: "Ventnor Avenue".to_owned(),
-            costs: vec![260],
+            costs: vec![260, 150],
             rents: vec![22, 110, 330, 800, 975, 1150],
             level: PropertyLevel::None,
             owner: None,
+            group: ColorGroup::Yellow,
+            mortgaged: false,
         },
         Tile::Utility {
             cost: 150,
-            owner: None
+            owner: None,
+            mortgaged: false,
         },
         Tile::Property {
             name: "Marvin Gardens".to_owned(),
-            costs: vec![280],
+            costs: vec![280, 150],
             rents: vec![24, 120, 360, 850, 1025, 1200],
             level: PropertyLevel::None,
             owner: None,
+            group: ColorGroup::Yellow,
+            mortgaged: false,
         },
         Tile::GoToJail,
         Tile::Property {
             name: "Pacific Avenue".to_owned(),
-            costs: vec![300],
+            costs: vec![300, 200],
             rents: vec![26, 130, 390, 900, 1100, 1275],
             level: PropertyLevel::None,
             owner: None,
+            group: ColorGroup::Green,
+            mortgaged: false,
         },
         Tile::Property {
             name: "North Carolina Avenue".to_owned(),
-            costs: vec![300],
+            costs: vec![300, 200],
             rents: vec![26, 130, 390, 900, 1100, 1275],
             level: PropertyLevel::None,
             owner: None,
+            group: ColorGroup::Green,
+            mortgaged: false,
         },
         Tile::Chance("Community Chest".to_owned()),
         Tile::Property {
             name: "Pennsylvania Avenue".to_owned(),
-            costs: vec![320],
+            costs: vec![320, 200],
             rents: vec![28, 150, 450, 1000, 1200, 1400],
             level: PropertyLevel::None,
             owner: None,
+            group: ColorGroup::Green,
+            mortgaged: false,
         },
         Tile::Railroad {
             owner: None,
             cost: 200,
             rents: vec![25, 50, 100, 200],
+            mortgaged: false,
         },
         Tile::Chance("Chance".to_owned()),
         Tile::Property {
             name: "Park Place".to_owned(),
-            costs: vec![350],
+            costs: vec![350, 200],
             rents: vec![35, 175, 500, 1100, 1300, 1500],
             level: PropertyLevel::None,
             owner: None,
+            group: ColorGroup::DarkBlue,
+            mortgaged: false,
         },
         Tile::LuxuryTax { price: 100 },
         Tile::Property {
             name: "Boardwalk".to_owned(),
-            costs: vec![400],
+            costs: vec![400, 200],
             rents: vec![50, 200, 600, 1400, 1700, 2000],
             level: PropertyLevel::None,
             owner: None,
+            group: ColorGroup::DarkBlue,
+            mortgaged: false,
         },
     ];
 }
@@ -1,3 +1,4 @@
+pub mod loader;
 pub mod map1;
 pub mod map_go;
 pub mod map_jail;
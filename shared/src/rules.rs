@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// House rules of a game, missing fields of a rules file keep their default value
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct GameRules {
+    pub starting_money: u32,
+    // Replaces the amount written on the Go tiles when set
+    pub go_salary: Option<u32>,
+    // Landing exactly on Go pays the salary twice
+    pub double_go_on_landing: bool,
+    pub jail_fine: u32,
+    // Failed rolls before the fine must be paid
+    pub jail_turns: u8,
+    // Taxes and fines go to a pot collected by landing on Free Parking
+    pub free_parking_jackpot: bool,
+    // Declined properties are auctioned, otherwise they stay with the bank
+    pub mandatory_auctions: bool,
+    pub even_build: bool,
+    // The Tax tile lets the player pick between its amount and a share of their net worth
+    pub income_tax_choice: bool,
+    pub income_tax_percent: u32,
+    // Rent multipliers of the dice roll for one and two utilities owned
+    pub utility_multipliers: [u32; 2],
+    // A third die adds Mr. Monopoly, Bus and triple moves
+    pub speed_die: bool,
+    // The game stops after this many turns, the best net worth wins
+    pub max_turns: Option<usize>,
+    // The game stops after this many minutes, the best net worth wins
+    pub time_limit_minutes: Option<u64>,
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        GameRules {
+            starting_money: 1500,
+            go_salary: None,
+            double_go_on_landing: false,
+            jail_fine: 50,
+            jail_turns: 3,
+            free_parking_jackpot: false,
+            mandatory_auctions: true,
+            even_build: true,
+            income_tax_choice: false,
+            income_tax_percent: 10,
+            utility_multipliers: [4, 10],
+            speed_die: false,
+            max_turns: None,
+            time_limit_minutes: None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RulesError {
+    NoJailTurns,
+    IncomeTaxPercent(u32),
+    UtilityMultipliers,
+    NoMaxTurns,
+    NoTimeLimit,
+}
+
+impl fmt::Display for RulesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RulesError::NoJailTurns => write!(f, "jail_turns must be at least 1"),
+            RulesError::IncomeTaxPercent(percent) => {
+                write!(f, "income_tax_percent must be at most 100, found {percent}")
+            }
+            RulesError::UtilityMultipliers => write!(
+                f,
+                "utility_multipliers must be positive, the second at least the first"
+            ),
+            RulesError::NoMaxTurns => write!(f, "max_turns must be at least 1"),
+            RulesError::NoTimeLimit => write!(f, "time_limit_minutes must be at least 1"),
+        }
+    }
+}
+
+impl GameRules {
+    // Values a game can't be played with
+    pub fn validate(&self) -> Result<(), RulesError> {
+        if self.jail_turns == 0 {
+            return Err(RulesError::NoJailTurns);
+        }
+        if self.income_tax_percent > 100 {
+            return Err(RulesError::IncomeTaxPercent(self.income_tax_percent));
+        }
+        let [one, two] = self.utility_multipliers;
+        if one == 0 || two < one {
+            return Err(RulesError::UtilityMultipliers);
+        }
+        if self.max_turns == Some(0) {
+            return Err(RulesError::NoMaxTurns);
+        }
+        if self.time_limit_minutes == Some(0) {
+            return Err(RulesError::NoTimeLimit);
+        }
+        Ok(())
+    }
+}